@@ -0,0 +1,91 @@
+//! Parse and solve every registered day, on its real input and on each example,
+//! then time the hot spots of the slower solvers on their own.
+//!
+//! Days that parse while solving keep the no-op `Solution::parse`, so only days
+//! whose `Solution::has_parser` is true get a `parse` benchmark.
+//!
+//! Run with `cargo bench`, `cargo bench -- day08` for a single day, or
+//! `cargo bench -- hot` for the hot spots only.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use aoc_2022::day03::{rucksack, Day03};
+use aoc_2022::day08::tree_top::{self, Direction, Position};
+use aoc_2022::day08::Day08;
+use aoc_2022::{Part, Solution, SOLUTIONS};
+
+/// The real input followed by every example, named for the benchmark ids
+fn inputs(solution: &dyn Solution) -> Vec<(String, &'static str)> {
+    let examples = solution
+        .examples()
+        .iter()
+        .enumerate()
+        .map(|(index, example)| (format!("example{}", index + 1), example.input));
+    [("input".to_string(), solution.input())]
+        .into_iter()
+        .chain(examples)
+        .collect()
+}
+
+fn days(c: &mut Criterion) {
+    for solution in SOLUTIONS {
+        let mut group = c.benchmark_group(format!("day{:02}", solution.day()));
+        for (name, input) in inputs(*solution) {
+            if solution.has_parser() {
+                group.bench_with_input(BenchmarkId::new("parse", &name), input, |b, input| b.iter(|| solution.parse(black_box(input))));
+            }
+            for part in [Part::One, Part::Two] {
+                let id = BenchmarkId::new(format!("part{}", part.number()), &name);
+                group.bench_with_input(id, input, |b, input| b.iter(|| solution.solve(part, black_box(input))));
+            }
+        }
+        group.finish();
+    }
+}
+
+/// Day 3 compares every item of one compartment with every item of the other
+fn rucksack_intersection(c: &mut Criterion) {
+    let compartments = Day03
+        .input()
+        .lines()
+        .map(rucksack::compartmentize)
+        .collect::<Vec<_>>();
+    c.bench_function("hot/day03/intersection", |b| {
+        b.iter(|| {
+            compartments
+                .iter()
+                .map(|(a, b)| rucksack::intersection(black_box(a), black_box(b)).len())
+                .sum::<usize>()
+        })
+    });
+}
+
+/// Day 8 walks the tree grid from every tree towards each edge
+fn tree_top_grid(c: &mut Criterion) {
+    let input = Day08.input();
+    let grid = tree_top::parse_tree(input).unwrap();
+    let size = input.lines().count() as i16;
+    let center = Position::new(size / 2, size / 2);
+    let directions = [Direction::North, Direction::South, Direction::East, Direction::West];
+    let mut group = c.benchmark_group("hot/day08");
+    group.bench_function("parse_tree", |b| b.iter(|| tree_top::parse_tree(black_box(input))));
+    group.bench_function("cast_ray", |b| {
+        b.iter(|| {
+            directions
+                .iter()
+                .map(|direction| tree_top::cast_ray(black_box(&center), *direction, &grid).len())
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("calculate_scenic_score", |b| {
+        b.iter(|| {
+            grid.keys()
+                .map(|position| tree_top::calculate_scenic_score(black_box(position), &grid))
+                .max()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, days, rucksack_intersection, tree_top_grid);
+criterion_main!(benches);
@@ -0,0 +1,162 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+
+use serde_json::{json, Value};
+
+use crate::{runner::Record, table};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting every allocation it hands out.
+///
+/// Installed as the global allocator by the binary when built with the
+/// `alloc-stats` feature; otherwise nothing is counted.
+pub struct Counting;
+
+fn allocated(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    /// Counts as a fresh allocation of `new_size` bytes that frees the old block
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+            allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Whether this build counts allocations at all
+pub const fn enabled() -> bool {
+    cfg!(feature = "alloc-stats")
+}
+
+/// Allocations made while running one closure
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    /// Most bytes live at once, above what was live before the closure ran
+    pub peak: usize,
+}
+
+impl AllocStats {
+    /// Adds `allocations`, `bytes` and `peak_bytes` to a JSON object
+    pub fn extend_json(&self, value: &mut Value) {
+        if let Value::Object(object) = value {
+            let stats = json!({
+                "allocations": self.allocations,
+                "bytes": self.bytes,
+                "peak_bytes": self.peak,
+            });
+            if let Value::Object(stats) = stats {
+                object.extend(stats);
+            }
+        }
+    }
+}
+
+/// Runs `f`, counting what it allocates.  Only meaningful while nothing else
+/// allocates concurrently, since the counters are process wide.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let baseline = LIVE.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    let value = f();
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak: PEAK
+            .load(Ordering::Relaxed)
+            .saturating_sub(baseline),
+    };
+    (value, stats)
+}
+
+/// Renders allocation counts per day and part as an aligned table
+pub fn table(records: &[Record], stats: &[AllocStats]) -> String {
+    let rows = records
+        .iter()
+        .zip(stats)
+        .map(|(record, stats)| {
+            [
+                record.day.to_string(),
+                record.part.to_string(),
+                stats.allocations.to_string(),
+                stats.bytes.to_string(),
+                stats.peak.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    table::render(["Day", "Part", "Allocations", "Bytes", "Peak live bytes"], &rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The only test touching the counters: `Counting` is not the global
+    // allocator under `cargo test`, so only these calls move them.
+    #[test]
+    pub fn test_measure() {
+        let layout = |size| Layout::from_size_align(size, 8).unwrap();
+        let ((), stats) = measure(|| unsafe {
+            let a = Counting.alloc(layout(100));
+            let a = Counting.realloc(a, layout(100), 200);
+            let b = Counting.alloc_zeroed(layout(50));
+            Counting.dealloc(a, layout(200));
+            Counting.dealloc(b, layout(50));
+            let c = Counting.alloc(layout(10));
+            Counting.dealloc(c, layout(10));
+        });
+        assert_eq!(
+            stats,
+            AllocStats {
+                allocations: 4,
+                bytes: 360,
+                peak: 250,
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_extend_json() {
+        let stats = AllocStats { allocations: 3, bytes: 120, peak: 64 };
+        let mut value = json!({"day": 1});
+        stats.extend_json(&mut value);
+        assert_eq!(value, json!({"day": 1, "allocations": 3, "bytes": 120, "peak_bytes": 64}));
+    }
+}
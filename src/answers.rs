@@ -0,0 +1,276 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{runner::Record, table, Part};
+
+/// Known-good answers for each day, stored as TOML:
+///
+/// ```toml
+/// [day01]
+/// part1 = "71023"
+/// part2 = "206289"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Answers {
+    #[serde(flatten)]
+    days: BTreeMap<String, DayAnswers>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DayAnswers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+    /// Answers the server rejected, so they are never submitted again
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wrong: Vec<WrongAnswer>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct WrongAnswer {
+    pub part: usize,
+    pub answer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<Hint>,
+}
+
+/// What the server said about a wrong answer
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooHigh => write!(f, "too high"),
+            Self::TooLow => write!(f, "too low"),
+        }
+    }
+}
+
+impl DayAnswers {
+    fn get_mut(&mut self, part: Part) -> &mut Option<String> {
+        match part {
+            Part::One => &mut self.part1,
+            Part::Two => &mut self.part2,
+        }
+    }
+}
+
+fn key(day: usize) -> String {
+    format!("day{day:02}")
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> io::Result<Self> {
+        toml::from_str(text).map_err(|error| io::Error::new(ErrorKind::InvalidData, error))
+    }
+
+    /// Loads the store, treating a missing file as empty
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let text = toml::to_string(self).map_err(|error| io::Error::new(ErrorKind::InvalidData, error))?;
+        fs::write(path, text)
+    }
+
+    pub fn get(&self, day: usize, part: Part) -> Option<&str> {
+        let answers = self.days.get(&key(day))?;
+        match part {
+            Part::One => answers.part1.as_deref(),
+            Part::Two => answers.part2.as_deref(),
+        }
+    }
+
+    pub fn set(&mut self, day: usize, part: Part, answer: &str) {
+        let answers = self.days.entry(key(day)).or_default();
+        *answers.get_mut(part) = Some(answer.to_string());
+    }
+
+    pub fn reject(&mut self, day: usize, part: Part, answer: &str, hint: Option<Hint>) {
+        let answers = self.days.entry(key(day)).or_default();
+        answers.wrong.push(WrongAnswer {
+            part: part.number(),
+            answer: answer.to_string(),
+            hint,
+        });
+    }
+
+    /// Explains why `answer` is already known to be wrong, using both exact
+    /// rejections and the too high / too low bounds they imply
+    pub fn known_wrong(&self, day: usize, part: Part, answer: &str) -> Option<String> {
+        let answers = self.days.get(&key(day))?;
+        let wrong = answers
+            .wrong
+            .iter()
+            .filter(|wrong| wrong.part == part.number());
+        for wrong in wrong {
+            if wrong.answer == answer {
+                return Some(format!("`{answer}` was already rejected"));
+            }
+            let (Ok(value), Ok(rejected)) = (answer.parse::<i64>(), wrong.answer.parse::<i64>()) else {
+                continue;
+            };
+            match wrong.hint {
+                Some(Hint::TooHigh) if value >= rejected => return Some(format!("`{answer}` is not below `{rejected}`, which was too high")),
+                Some(Hint::TooLow) if value <= rejected => return Some(format!("`{answer}` is not above `{rejected}`, which was too low")),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Compares each record against the stored answer for its day and part
+    pub fn verify(&self, records: &[Record]) -> Vec<Verification> {
+        records
+            .iter()
+            .map(|record| {
+                let expected = self.get(record.day, record.part);
+                let status = match (expected, &record.answer) {
+                    (None, _) => Status::Missing,
+                    (Some(expected), Ok(actual)) if expected == actual => Status::Pass,
+                    (Some(_), _) => Status::Fail,
+                };
+                Verification {
+                    record: record.clone(),
+                    expected: expected.map(|e| e.to_string()),
+                    status,
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail => write!(f, "fail"),
+            Self::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Verification {
+    pub record: Record,
+    pub expected: Option<String>,
+    pub status: Status,
+}
+
+/// Renders verifications as an aligned pass/fail/missing report
+pub fn report(verifications: &[Verification]) -> String {
+    let rows = verifications
+        .iter()
+        .map(|verification| {
+            let actual = match &verification.record.answer {
+                Ok(answer) => answer.clone(),
+                Err(error) => format!("failed: {error}"),
+            };
+            [
+                verification.record.day.to_string(),
+                verification.record.part.to_string(),
+                verification
+                    .expected
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+                actual,
+                verification.status.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    table::render(["Day", "Part", "Expected", "Actual", "Status"], &rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{input::Source, runner, SOLUTIONS};
+
+    #[test]
+    pub fn test_parse_and_set() {
+        let mut answers = Answers::parse("[day01]\npart1 = \"24000\"\n").unwrap();
+        assert_eq!(answers.get(1, Part::One), Some("24000"));
+        assert_eq!(answers.get(1, Part::Two), None);
+        answers.set(1, Part::Two, "45000");
+        answers.set(10, Part::One, "13140");
+        let reparsed = Answers::parse(&toml::to_string(&answers).unwrap()).unwrap();
+        assert_eq!(reparsed, answers);
+        assert_eq!(reparsed.get(10, Part::One), Some("13140"));
+    }
+
+    #[test]
+    pub fn test_known_wrong() {
+        let mut answers = Answers::default();
+        answers.reject(1, Part::One, "100", Some(Hint::TooHigh));
+        answers.reject(1, Part::One, "20", Some(Hint::TooLow));
+        answers.reject(1, Part::One, "ABC", None);
+        assert!(answers.known_wrong(1, Part::One, "ABC").is_some());
+        assert!(answers.known_wrong(1, Part::One, "150").is_some());
+        assert!(answers.known_wrong(1, Part::One, "20").is_some());
+        assert!(answers.known_wrong(1, Part::One, "50").is_none());
+        assert!(answers.known_wrong(1, Part::Two, "150").is_none());
+        assert!(answers.known_wrong(2, Part::One, "ABC").is_none());
+
+        let reparsed = Answers::parse(&toml::to_string(&answers).unwrap()).unwrap();
+        assert_eq!(reparsed, answers);
+    }
+
+    #[test]
+    pub fn test_verify_statuses() {
+        let answers = Answers::parse("[day04]\npart1 = \"2\"\n\n[day05]\npart1 = \"wrong\"\n").unwrap();
+        let input = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+        let records = [
+            runner::run(SOLUTIONS[3], Part::One, input),
+            runner::run(SOLUTIONS[3], Part::Two, input),
+            runner::run(SOLUTIONS[4], Part::One, SOLUTIONS[4].input()),
+        ];
+        let statuses = answers
+            .verify(&records)
+            .iter()
+            .map(|verification| verification.status)
+            .collect::<Vec<_>>();
+        assert_eq!(statuses, [Status::Pass, Status::Missing, Status::Fail]);
+    }
+
+    #[test]
+    pub fn test_stored_answers() {
+        let answers = Answers::parse(include_str!("../answers.toml")).unwrap();
+        let records = SOLUTIONS
+            .iter()
+            // Scaffolded days have no answers to store yet
+            .filter(|solution| solution.solved())
+            // Read like `verify` does, so the stored answers are checked against normalized input
+            .flat_map(|solution| {
+                let input = Source::Embedded.read(*solution).unwrap();
+                [Part::One, Part::Two].map(|part| runner::run(*solution, part, &input))
+            })
+            .collect::<Vec<_>>();
+        for verification in answers.verify(&records) {
+            assert_eq!(verification.status, Status::Pass, "{verification:?}");
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::{
+    fmt,
+    hint::black_box,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use serde_json::{json, Value};
+
+use crate::{runner::panic_message, table, Part, Parts, Solution};
+
+/// What was timed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Part(Part),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse => write!(f, "parse"),
+            Self::Part(part) => write!(f, "part {part}"),
+        }
+    }
+}
+
+/// Summary statistics over repeated wall time samples
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let count = sorted.len();
+        let median = match count % 2 {
+            0 => (sorted[count / 2 - 1] + sorted[count / 2]) / 2,
+            _ => sorted[count / 2],
+        };
+        let mean = sorted.iter().sum::<Duration>() / count as u32;
+        // Nearest-rank percentile
+        let p95 = sorted[(count * 95).div_ceil(100) - 1];
+        Some(Self { min: sorted[0], median, mean, p95 })
+    }
+}
+
+/// Timings for one phase of one day
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Timing {
+    pub day: usize,
+    pub phase: Phase,
+    /// The statistics, or the panic message if the phase failed
+    pub stats: Result<Stats, String>,
+}
+
+impl Timing {
+    /// A flat JSON object with durations in seconds; the stats are null when `error` is set
+    pub fn to_json(&self) -> Value {
+        let part = match self.phase {
+            Phase::Parse => None,
+            Phase::Part(part) => Some(part.number()),
+        };
+        let mut value = json!({
+            "day": self.day,
+            "phase": self.phase.to_string(),
+            "part": part,
+            "min": null,
+            "median": null,
+            "mean": null,
+            "p95": null,
+            "error": null,
+        });
+        match &self.stats {
+            Ok(stats) => {
+                value["min"] = json!(stats.min.as_secs_f64());
+                value["median"] = json!(stats.median.as_secs_f64());
+                value["mean"] = json!(stats.mean.as_secs_f64());
+                value["p95"] = json!(stats.p95.as_secs_f64());
+            }
+            Err(error) => value["error"] = json!(error),
+        }
+        value
+    }
+}
+
+/// Runs parsing (for days with a parser) and each selected part `iterations`
+/// times and summarizes the wall time
+pub fn bench(solution: &dyn Solution, parts: Parts, input: &str, iterations: usize) -> Vec<Timing> {
+    let phases = solution
+        .has_parser()
+        .then_some(Phase::Parse)
+        .into_iter()
+        .chain(parts.iter().map(Phase::Part));
+    phases
+        .map(|phase| {
+            let samples = (0..iterations.max(1))
+                .map(|_| {
+                    let sample = panic::catch_unwind(AssertUnwindSafe(|| {
+                        let start = Instant::now();
+                        let result = match phase {
+                            Phase::Parse => solution.parse(black_box(input)),
+                            Phase::Part(part) => black_box(solution.solve(part, black_box(input))).map(|_| ()),
+                        };
+                        result.map(|_| start.elapsed())
+                    }))
+                    .map_err(panic_message)?;
+                    sample.map_err(|error| error.to_string())
+                })
+                .collect::<Result<Vec<_>, _>>();
+            Timing {
+                day: solution.day(),
+                phase,
+                stats: samples.map(|samples| Stats::from_samples(&samples).unwrap()),
+            }
+        })
+        .collect()
+}
+
+/// Renders timings as an aligned table
+pub fn table(timings: &[Timing]) -> String {
+    let rows = timings
+        .iter()
+        .map(|timing| match &timing.stats {
+            Ok(stats) => [
+                timing.day.to_string(),
+                timing.phase.to_string(),
+                format!("{:.3?}", stats.min),
+                format!("{:.3?}", stats.median),
+                format!("{:.3?}", stats.mean),
+                format!("{:.3?}", stats.p95),
+            ],
+            Err(message) => [timing.day.to_string(), timing.phase.to_string(), "-".into(), "-".into(), "-".into(), format!("failed: {message}")],
+        })
+        .collect::<Vec<_>>();
+    table::render(["Day", "Phase", "Min", "Median", "Mean", "P95"], &rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::find;
+
+    use rstest::rstest;
+
+    fn ms(values: &[u64]) -> Vec<Duration> {
+        values
+            .iter()
+            .map(|v| Duration::from_millis(*v))
+            .collect()
+    }
+
+    #[rstest]
+    #[case(&[5], (5, 5, 5, 5))]
+    #[case(&[4, 1, 3, 2], (1, 2, 2, 4))]
+    #[case(&[3, 1, 2], (1, 2, 2, 3))]
+    #[case(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20], (1, 10, 10, 19))]
+    pub fn test_stats(#[case] samples: &[u64], #[case] expected: (u64, u64, u64, u64)) {
+        let stats = Stats::from_samples(&ms(samples)).unwrap();
+        let (min, median, mean, p95) = expected;
+        let actual = [stats.min, stats.median, stats.mean, stats.p95].map(|d| d.as_millis() as u64);
+        assert_eq!(actual, [min, median, mean, p95]);
+    }
+
+    #[test]
+    pub fn test_stats_empty() {
+        assert!(Stats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    pub fn test_bench_phases() {
+        let solution = find(8).unwrap();
+        let timings = bench(solution, Parts::Two, solution.example(1).unwrap(), 3);
+        let phases = timings
+            .iter()
+            .map(|timing| timing.phase)
+            .collect::<Vec<_>>();
+        assert_eq!(phases, [Phase::Parse, Phase::Part(Part::Two)]);
+        assert!(timings.iter().all(|timing| timing.stats.is_ok()));
+    }
+
+    #[test]
+    pub fn test_bench_skips_missing_parser() {
+        let solution = find(6).unwrap();
+        let timings = bench(solution, Parts::Both, solution.example(1).unwrap(), 1);
+        let phases = timings
+            .iter()
+            .map(|timing| timing.phase)
+            .collect::<Vec<_>>();
+        assert_eq!(phases, [Phase::Part(Part::One), Phase::Part(Part::Two)]);
+    }
+}
@@ -0,0 +1,97 @@
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone};
+
+pub const YEAR: i32 = 2022;
+pub const LAST_DAY: usize = 25;
+
+/// Puzzles unlock at midnight US Eastern (UTC-5), regardless of daylight saving
+const UNLOCK_OFFSET_SECONDS: i32 = -5 * 60 * 60;
+
+/// Where a moment falls relative to the event
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventDay {
+    /// The first puzzle has not unlocked yet
+    Before,
+    /// The most recently unlocked puzzle
+    Day(usize),
+    /// The last puzzle unlocked more than a day ago
+    After,
+}
+
+pub fn event_day<Tz: TimeZone>(now: &DateTime<Tz>) -> EventDay {
+    let unlock_zone = FixedOffset::east_opt(UNLOCK_OFFSET_SECONDS).unwrap();
+    let date = now.with_timezone(&unlock_zone).date_naive();
+    match (date.year(), date.month(), date.day() as usize) {
+        (year, _, _) if year < YEAR => EventDay::Before,
+        (YEAR, 12, day) if day <= LAST_DAY => EventDay::Day(day),
+        (YEAR, 12, _) => EventDay::After,
+        (YEAR, _, _) => EventDay::Before,
+        _ => EventDay::After,
+    }
+}
+
+/// Picks the day to run when none was requested, with an explanation when it
+/// is not simply today's puzzle
+pub fn default_day<Tz: TimeZone>(now: &DateTime<Tz>, latest_implemented: usize) -> (usize, Option<String>) {
+    match event_day(now) {
+        EventDay::Day(day) if day <= latest_implemented => (day, None),
+        EventDay::Day(day) => (
+            latest_implemented,
+            Some(format!("Day {day} is not implemented yet. Running latest implemented day (day={latest_implemented})")),
+        ),
+        EventDay::Before => (
+            latest_implemented,
+            Some(format!("Advent of Code {YEAR} has not started yet. Running latest implemented day (day={latest_implemented})")),
+        ),
+        EventDay::After => (
+            latest_implemented,
+            Some(format!("Advent of Code {YEAR} is over. Running latest implemented day (day={latest_implemented})")),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::Local;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("2021-12-10T12:00:00Z", EventDay::Before)]
+    #[case("2022-06-01T12:00:00Z", EventDay::Before)]
+    #[case("2022-12-01T04:59:59Z", EventDay::Before)]
+    #[case("2022-12-01T05:00:00Z", EventDay::Day(1))]
+    #[case("2022-12-09T04:59:59Z", EventDay::Day(8))]
+    #[case("2022-12-09T05:00:00Z", EventDay::Day(9))]
+    #[case("2022-12-25T05:00:00Z", EventDay::Day(25))]
+    #[case("2022-12-26T04:59:59Z", EventDay::Day(25))]
+    #[case("2022-12-26T05:00:00Z", EventDay::After)]
+    #[case("2023-01-01T12:00:00Z", EventDay::After)]
+    #[case("2023-12-05T12:00:00Z", EventDay::After)]
+    pub fn test_event_day(#[case] now: &str, #[case] expected: EventDay) {
+        let now = DateTime::parse_from_rfc3339(now).unwrap();
+        assert_eq!(event_day(&now), expected);
+    }
+
+    #[rstest]
+    #[case("2022-12-04T12:00:00Z", (4, false))]
+    #[case("2022-12-09T12:00:00Z", (9, false))]
+    #[case("2022-12-15T12:00:00Z", (9, true))]
+    #[case("2022-11-15T12:00:00Z", (9, true))]
+    #[case("2024-10-01T12:00:00Z", (9, true))]
+    pub fn test_default_day(#[case] now: &str, #[case] expected: (usize, bool)) {
+        let now = DateTime::parse_from_rfc3339(now).unwrap();
+        let (day, note) = default_day(&now, 9);
+        assert_eq!((day, note.is_some()), expected);
+    }
+
+    #[rstest]
+    #[case("2022-12-09T06:00:00+01:00", EventDay::Day(9))]
+    #[case("2022-12-09T13:30:00+09:30", EventDay::Day(8))]
+    #[case("2022-12-08T22:00:00-08:00", EventDay::Day(9))]
+    pub fn test_event_day_is_timezone_independent(#[case] now: &str, #[case] expected: EventDay) {
+        let now = DateTime::parse_from_rfc3339(now).unwrap();
+        assert_eq!(event_day(&now), expected);
+        assert_eq!(event_day(&now.with_timezone(&Local)), expected);
+    }
+}
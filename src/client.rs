@@ -0,0 +1,504 @@
+use std::{env, fmt, fs, io, path::PathBuf, time::Duration};
+
+use tracing::{debug, info};
+
+use crate::{
+    answers::{Answers, Hint},
+    calendar::LAST_DAY,
+    Part,
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies this tool to the Advent of Code servers, as their maintainer asks
+pub const USER_AGENT: &str = concat!("github.com/brianbruggeman/aoc-2022 (", env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"), ")");
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// No session token in the environment or config file
+    MissingSession,
+    /// Not one of the event's days, so there is no input to ask for
+    InvalidDay(usize),
+    /// The server answered with an error status
+    Status(u16, String),
+    /// The request never got an answer
+    Transport(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSession => write!(f, "No session token: set AOC_SESSION or write it to {}", session_file().display()),
+            Self::InvalidDay(day) => write!(f, "Day {day} is not a day of the event (expected 1-{LAST_DAY})"),
+            Self::Status(status, body) => write!(f, "Server responded {status}: {}", body.trim()),
+            Self::Transport(error) => write!(f, "Request failed: {error}"),
+            Self::Io(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<io::Error> for ClientError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(error: ureq::Error) -> Self {
+        match error {
+            ureq::Error::Status(status, response) => Self::Status(status, response.into_string().unwrap_or_default()),
+            ureq::Error::Transport(transport) => Self::Transport(transport.to_string()),
+        }
+    }
+}
+
+fn home() -> PathBuf {
+    env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// `$XDG_CONFIG_HOME/aoc-2022/session`, falling back to `~/.config`
+pub fn session_file() -> PathBuf {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home().join(".config"))
+        .join("aoc-2022")
+        .join("session")
+}
+
+/// `$AOC_CACHE_DIR`, else `$XDG_CACHE_HOME/aoc-2022`, falling back to `~/.cache`
+pub fn cache_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("AOC_CACHE_DIR") {
+        return PathBuf::from(dir);
+    }
+    env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home().join(".cache"))
+        .join("aoc-2022")
+}
+
+/// The session cookie value from `AOC_SESSION`, or else the session file
+pub fn session() -> Result<String, ClientError> {
+    let token = match env::var("AOC_SESSION") {
+        Ok(token) => token,
+        Err(_) => fs::read_to_string(session_file()).map_err(|_| ClientError::MissingSession)?,
+    };
+    match token.trim() {
+        "" => Err(ClientError::MissingSession),
+        token => Ok(token.to_string()),
+    }
+}
+
+/// Puzzle inputs on disk, one file per year and day
+pub struct Cache {
+    root: PathBuf,
+}
+
+impl Cache {
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn path(&self, year: i32, day: usize) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(format!("day{day:02}.txt"))
+    }
+
+    pub fn get(&self, year: i32, day: usize) -> Option<String> {
+        fs::read_to_string(self.path(year, day)).ok()
+    }
+
+    pub fn put(&self, year: i32, day: usize, input: &str) -> io::Result<PathBuf> {
+        let path = self.path(year, day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, input)?;
+        Ok(path)
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent,
+        }
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
+    }
+
+    pub(crate) fn get(&self, path: &str) -> Result<String, ClientError> {
+        let url = self.url(path);
+        debug!("GET {url}");
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?;
+        Ok(response.into_string()?)
+    }
+
+    pub(crate) fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String, ClientError> {
+        let url = self.url(path);
+        debug!("POST {url}");
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(form)?;
+        Ok(response.into_string()?)
+    }
+
+    pub fn download_input(&self, year: i32, day: usize) -> Result<String, ClientError> {
+        self.get(&format!("/{year}/day/{day}/input"))
+    }
+
+    pub fn submit_answer(&self, year: i32, day: usize, part: Part, answer: &str) -> Result<Verdict, ClientError> {
+        let level = part.number().to_string();
+        let html = self.post_form(&format!("/{year}/day/{day}/answer"), &[("level", &level), ("answer", answer)])?;
+        Ok(Verdict::parse(&html))
+    }
+}
+
+/// How the server judged a submitted answer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong(Option<Hint>),
+    /// Submitted too soon after a previous answer; try again after the wait
+    RateLimited(Duration),
+    /// The part was already solved, or is not unlocked yet
+    WrongLevel,
+    /// Not submitted, because the answer store already rules it out
+    Skipped(String),
+    /// A response this client does not recognize, with its main text
+    Unknown(String),
+}
+
+impl Verdict {
+    /// Interprets the `<article>` text of an answer response page
+    pub fn parse(html: &str) -> Self {
+        let text = article_text(html);
+        if text.contains("That's the right answer") {
+            Self::Correct
+        } else if text.contains("That's not the right answer") {
+            let hint = match (text.contains("too high"), text.contains("too low")) {
+                (true, _) => Some(Hint::TooHigh),
+                (_, true) => Some(Hint::TooLow),
+                _ => None,
+            };
+            Self::Wrong(hint)
+        } else if text.contains("You gave an answer too recently") {
+            Self::RateLimited(parse_wait(&text).unwrap_or(Duration::from_secs(60)))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Self::WrongLevel
+        } else {
+            Self::Unknown(text)
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "Correct"),
+            Self::Wrong(Some(hint)) => write!(f, "Wrong: answer is {hint}"),
+            Self::Wrong(None) => write!(f, "Wrong"),
+            Self::RateLimited(wait) => write!(f, "Rate limited: wait {}s before submitting again", wait.as_secs()),
+            Self::WrongLevel => write!(f, "Part already solved or not unlocked yet"),
+            Self::Skipped(reason) => write!(f, "Not submitted: {reason}"),
+            Self::Unknown(text) => write!(f, "Unrecognized response: {text}"),
+        }
+    }
+}
+
+/// The text inside `<article>`, with tags stripped and whitespace collapsed
+fn article_text(html: &str) -> String {
+    let article = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Reads `1m 23s` out of `...You have 1m 23s left to wait.`
+fn parse_wait(text: &str) -> Option<Duration> {
+    let end = text.find(" left to wait")?;
+    let start = text[..end].rfind("have ")? + "have ".len();
+    let mut seconds = 0;
+    for token in text[start..end].split_whitespace() {
+        let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+        let value = value.parse::<u64>().ok()?;
+        seconds += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+/// Submits an answer unless the store already knows the outcome, then records the verdict
+pub fn submit(client: &Client, answers: &mut Answers, year: i32, day: usize, part: Part, answer: &str) -> Result<Verdict, ClientError> {
+    if let Some(known) = answers.get(day, part) {
+        let reason = match known == answer {
+            true => format!("`{answer}` is already the accepted answer"),
+            false => format!("`{known}` is already the accepted answer"),
+        };
+        return Ok(Verdict::Skipped(reason));
+    }
+    if let Some(reason) = answers.known_wrong(day, part, answer) {
+        return Ok(Verdict::Skipped(reason));
+    }
+    let verdict = client.submit_answer(year, day, part, answer)?;
+    match &verdict {
+        Verdict::Correct => answers.set(day, part, answer),
+        Verdict::Wrong(hint) => answers.reject(day, part, answer, *hint),
+        _ => {}
+    }
+    Ok(verdict)
+}
+
+/// Rejects days outside the event before anything is requested or cached
+pub fn check_day(day: usize) -> Result<(), ClientError> {
+    match (1..=LAST_DAY).contains(&day) {
+        true => Ok(()),
+        false => Err(ClientError::InvalidDay(day)),
+    }
+}
+
+/// Returns the cached input for the day, downloading it only when it is not cached yet
+pub fn fetch(client: &Client, cache: &Cache, year: i32, day: usize) -> Result<PathBuf, ClientError> {
+    check_day(day)?;
+    let path = cache.path(year, day);
+    if cache.get(year, day).is_some() {
+        info!("Using cached input {}", path.display());
+        return Ok(path);
+    }
+    let input = client.download_input(year, day)?;
+    Ok(cache.put(year, day, &input)?)
+}
+
+pub fn default_cache() -> Cache {
+    Cache::new(cache_dir())
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc::{self, Receiver},
+        thread,
+    };
+
+    /// A request as the mock server saw it
+    #[derive(Debug)]
+    pub struct Request {
+        pub head: String,
+        pub body: String,
+    }
+
+    impl Request {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.head.lines().find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                key.eq_ignore_ascii_case(name)
+                    .then_some(value.trim())
+            })
+        }
+    }
+
+    /// Serves each canned `(status, body)` response to one connection, in order,
+    /// and reports every request it received
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut head = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    head.push_str(&line);
+                }
+                let request = Request { head, body: String::new() };
+                let length = request
+                    .header("Content-Length")
+                    .and_then(|length| length.parse::<usize>().ok())
+                    .unwrap_or(0);
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+                let request = Request {
+                    body: String::from_utf8(request_body).unwrap(),
+                    ..request
+                };
+                let mut stream = reader.into_inner();
+                write!(stream, "HTTP/1.1 {status} Canned\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len()).unwrap();
+                sender.send(request).unwrap();
+            }
+        });
+        (base_url, receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    fn temp_cache(name: &str) -> Cache {
+        let root = env::temp_dir().join(format!("aoc-2022-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        Cache::new(root)
+    }
+
+    #[test]
+    pub fn test_fetch_downloads_once() {
+        let (base_url, requests) = mock::serve(vec![(200, "1000\n2000\n")]);
+        let client = Client::new(&base_url, "abc123");
+        let cache = temp_cache("fetch");
+
+        let path = fetch(&client, &cache, 2022, 1).unwrap();
+        assert_eq!(path, cache.path(2022, 1));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+
+        let request = requests.recv().unwrap();
+        assert!(request.head.starts_with("GET /2022/day/1/input "));
+        assert_eq!(request.header("Cookie"), Some("session=abc123"));
+        assert_eq!(request.header("User-Agent"), Some(USER_AGENT));
+        assert!(request.body.is_empty());
+
+        // The mock only answers once; a second download would fail
+        assert_eq!(fetch(&client, &cache, 2022, 1).unwrap(), path);
+        fs::remove_dir_all(&cache.root).unwrap();
+    }
+
+    #[test]
+    pub fn test_fetch_error_is_not_cached() {
+        let (base_url, _requests) = mock::serve(vec![(400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.")]);
+        let client = Client::new(&base_url, "expired");
+        let cache = temp_cache("fetch-error");
+
+        let error = fetch(&client, &cache, 2022, 2).unwrap_err();
+        assert!(matches!(error, ClientError::Status(400, _)), "{error:?}");
+        assert!(!cache.path(2022, 2).exists());
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(26)]
+    pub fn test_fetch_rejects_invalid_day(#[case] day: usize) {
+        // Nothing listens here, so a request would fail differently
+        let client = Client::new("http://127.0.0.1:9", "abc123");
+        let cache = temp_cache("fetch-invalid");
+
+        let error = fetch(&client, &cache, 2022, day).unwrap_err();
+        assert!(matches!(error, ClientError::InvalidDay(invalid) if invalid == day), "{error:?}");
+        assert!(!cache.path(2022, day).exists());
+    }
+
+    const CORRECT: &str = r#"<html><body><main><article><p>That's the right answer!  You are <span class="day-success">one gold star</span> closer to collecting enough star fruit. <a href="/2022/day/1#part2">[Continue to Part Two]</a></p></article></main></body></html>"#;
+    const TOO_HIGH: &str = r#"<html><body><main><article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. <a href="/2022/day/1">[Return to Day 1]</a></p></article></main></body></html>"#;
+    const TOO_LOW: &str = r#"<main><article><p>That's not the right answer; your answer is too low.  Please wait one minute before trying again.</p></article></main>"#;
+    const WRONG: &str = r#"<main><article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p></article></main>"#;
+    const THROTTLED: &str = r#"<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait. <a href="/2022/day/1">[Return to Day 1]</a></p></article></main>"#;
+    const LEVEL: &str = r#"<main><article><p>You don't seem to be solving the right level.  Did you already complete it? <a href="/2022/day/1">[Return to Day 1]</a></p></article></main>"#;
+
+    #[rstest]
+    #[case(CORRECT, Verdict::Correct)]
+    #[case(TOO_HIGH, Verdict::Wrong(Some(Hint::TooHigh)))]
+    #[case(TOO_LOW, Verdict::Wrong(Some(Hint::TooLow)))]
+    #[case(WRONG, Verdict::Wrong(None))]
+    #[case(THROTTLED, Verdict::RateLimited(Duration::from_secs(83)))]
+    #[case(LEVEL, Verdict::WrongLevel)]
+    #[case("<main><article><p>Something new</p></article></main>", Verdict::Unknown("Something new".to_string()))]
+    pub fn test_parse_verdict(#[case] html: &str, #[case] expected: Verdict) {
+        assert_eq!(Verdict::parse(html), expected);
+    }
+
+    #[rstest]
+    #[case("You have 35s left to wait.", Some(35))]
+    #[case("You have 2m left to wait.", Some(120))]
+    #[case("You have 1h 2m 3s left to wait.", Some(3723))]
+    #[case("Please wait one minute.", None)]
+    pub fn test_parse_wait(#[case] text: &str, #[case] expected: Option<u64>) {
+        assert_eq!(parse_wait(text), expected.map(Duration::from_secs));
+    }
+
+    #[test]
+    pub fn test_submit_records_verdicts() {
+        let (base_url, requests) = mock::serve(vec![(200, TOO_HIGH), (200, THROTTLED), (200, CORRECT)]);
+        let client = Client::new(&base_url, "abc123");
+        let mut answers = Answers::default();
+
+        let verdict = submit(&client, &mut answers, 2022, 1, Part::One, "90000").unwrap();
+        assert_eq!(verdict, Verdict::Wrong(Some(Hint::TooHigh)));
+        let request = requests.recv().unwrap();
+        assert!(request
+            .head
+            .starts_with("POST /2022/day/1/answer "));
+        assert_eq!(request.body, "level=1&answer=90000");
+
+        // Ruled out locally by the too high bound, so the server is not asked
+        let verdict = submit(&client, &mut answers, 2022, 1, Part::One, "95000").unwrap();
+        assert!(matches!(verdict, Verdict::Skipped(_)), "{verdict:?}");
+
+        let verdict = submit(&client, &mut answers, 2022, 1, Part::One, "71023").unwrap();
+        assert_eq!(verdict, Verdict::RateLimited(Duration::from_secs(83)));
+        assert_eq!(answers.get(1, Part::One), None);
+
+        let verdict = submit(&client, &mut answers, 2022, 1, Part::One, "71023").unwrap();
+        assert_eq!(verdict, Verdict::Correct);
+        assert_eq!(answers.get(1, Part::One), Some("71023"));
+
+        let verdict = submit(&client, &mut answers, 2022, 1, Part::One, "71023").unwrap();
+        assert!(matches!(verdict, Verdict::Skipped(_)), "{verdict:?}");
+        assert_eq!(requests.iter().count(), 2);
+    }
+
+    #[test]
+    pub fn test_cache_path() {
+        let cache = Cache::new("/tmp/aoc");
+        assert_eq!(cache.path(2022, 9), PathBuf::from("/tmp/aoc/2022/day09.txt"));
+    }
+}
@@ -1,22 +1,22 @@
-use std::cmp::Reverse;
+use std::{cmp::Reverse, mem};
+
+use crate::AocError;
+
 static DEFAULT_INPUT: &str = include_str!("input.txt");
 
-pub fn calculate(input: Option<&str>, count: usize) -> usize {
+pub fn calculate(input: Option<&str>, count: usize) -> Result<usize, AocError> {
     let data = match input {
         Some(v) => v,
         None => DEFAULT_INPUT,
     };
-    let elves = parse(data);
-    let mut elf_calories = Vec::new();
-    for elf in elves.iter() {
-        let mut calories = 0;
-        for calorie_count in elf.split('\n') {
-            calories += calorie_count
-                .parse::<usize>()
-                .expect("Could not parse calorie");
-        }
-        elf_calories.push(calories);
+    let elves = parse(data)?;
+    if elves.is_empty() {
+        return Err(AocError::no_answer("No elves are carrying any calories"));
     }
+    let mut elf_calories = elves
+        .iter()
+        .map(|elf| elf.iter().sum::<usize>())
+        .collect::<Vec<_>>();
     elf_calories.sort_by_key(|w| Reverse(*w));
     let calories = elf_calories
         .iter()
@@ -24,24 +24,30 @@ pub fn calculate(input: Option<&str>, count: usize) -> usize {
         .filter(|(idx, _)| idx < &count)
         .map(|(_, value)| value)
         .sum();
-    calories
+    Ok(calories)
 }
 
-fn parse(input: &str) -> Vec<String> {
+/// Groups calorie counts by elf, where elves are separated by blank lines
+pub fn parse(input: &str) -> Result<Vec<Vec<usize>>, AocError> {
     let mut elves = Vec::new();
-    let mut values = "".to_string();
-    for line in input.split('\n') {
-        if line.trim() == "" {
-            elves.push(values);
-            values = "".to_string();
-        } else {
-            match values.is_empty() {
-                true => values = line.to_string(),
-                false => values = format!("{values}\n{line}"),
+    let mut values = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        let value = line.trim();
+        if value.is_empty() {
+            if !values.is_empty() {
+                elves.push(mem::take(&mut values));
             }
+            continue;
         }
+        let calories = value
+            .parse::<usize>()
+            .map_err(|_| AocError::parse(index, line, value, format!("`{value}` is not a calorie count")))?;
+        values.push(calories);
     }
-    elves
+    if !values.is_empty() {
+        elves.push(values);
+    }
+    Ok(elves)
 }
 
 #[cfg(test)]
@@ -51,19 +57,28 @@ mod test {
     use rstest::*;
 
     #[rstest]
-    #[case("a\nb\n", &["a\nb"])]
-    #[case("a\nb\n\nc", &["a\nb", "c"])]
-    #[case("a\nb\n\nc\nd", &["a\nb", "c\nd"])]
-    pub fn test_parse(#[case] input: &str, #[case] output: &[&str]) {
-        let result = parse(input);
-        // let output = output.iter().map(|s| s.to_string()).collect::<Vec<_>>();
-        for (r, o) in result.iter().zip(output) {
-            assert_eq!(r, o);
-        }
+    #[case("1\n2\n", vec![vec![1, 2]])]
+    #[case("1\n2\n\n3", vec![vec![1, 2], vec![3]])]
+    #[case("1\n2\n\n3\n4", vec![vec![1, 2], vec![3, 4]])]
+    #[case("1\r\n2\r\n\r\n3\r\n", vec![vec![1, 2], vec![3]])]
+    #[case("1\n\n\n3\n", vec![vec![1], vec![3]])]
+    pub fn test_parse(#[case] input: &str, #[case] output: Vec<Vec<usize>>) {
+        let result = parse(input).unwrap();
+        assert_eq!(result, output);
     }
 
     #[rstest]
-    #[case("1", 1, 0)]
+    #[case("1\n2\n\nabc\n", (4, 1), "`abc` is not a calorie count")]
+    #[case("1\n  -5\n", (2, 3), "`-5` is not a calorie count")]
+    pub fn test_parse_error(#[case] input: &str, #[case] position: (usize, usize), #[case] message: &str) {
+        let error = parse(input).unwrap_err();
+        assert_eq!((error.line, error.column), (Some(position.0), Some(position.1)));
+        assert_eq!(error.message, message);
+    }
+
+    #[rstest]
+    #[case("1", 1, 1)]
+    #[case("1\r\n2\r\n\r\n4", 1, 4)]
     #[case("1\n", 1, 1)]
     #[case("1\n2\n\n4\n", 1, 4)]
     #[case("1\n5\n\n3\n", 1, 6)]
@@ -71,6 +86,12 @@ mod test {
     #[case("1\n5\n\n3\n\n1\n\n1\n\n1\n", 2, 9)]
     pub fn test_calculate(#[case] text: &str, #[case] count: usize, #[case] output: usize) {
         let result = calculate(Some(text), count);
-        assert_eq!(result, output);
+        assert_eq!(result, Ok(output));
+    }
+
+    #[test]
+    pub fn test_calculate_without_elves() {
+        let error = calculate(Some("\n\n"), 1).unwrap_err();
+        assert_eq!(error.kind, crate::ErrorKind::NoAnswer);
     }
 }
@@ -1,7 +1,47 @@
-mod calories;
+pub mod calories;
 
-pub fn run() {
-    println!("Day 1:");
-    println!("    Maximum: {} calories", calories::calculate(None, 1));
-    println!("      Max 3: {} calories", calories::calculate(None, 3));
+use std::hint::black_box;
+
+use crate::{AocError, Example, Solution};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    fn day(&self) -> usize {
+        1
+    }
+    fn title(&self) -> &'static str {
+        "Calorie Counting"
+    }
+    fn input(&self) -> &'static str {
+        include_str!("input.txt")
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: include_str!("example.txt"),
+            part1: Some("24000"),
+            part2: Some("45000"),
+        }]
+    }
+    fn labels(&self) -> [&'static str; 2] {
+        ["Maximum calories", "Max 3 calories"]
+    }
+    fn has_parser(&self) -> bool {
+        true
+    }
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        black_box(calories::parse(input)?);
+        Ok(())
+    }
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok(calories::calculate(Some(input), 1)?.to_string())
+    }
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok(calories::calculate(Some(input), 3)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    crate::solution::example_tests!(super::Day01);
 }
@@ -3,9 +3,39 @@ pub mod outcome;
 pub mod player;
 pub mod rock_paper;
 
-pub fn run() {
-    let moves = include_str!("input1.txt");
-    println!("Day 2:");
-    println!("    Guessed: score {}", rock_paper::play_round_1(moves));
-    println!("    Actual: score {}", rock_paper::play_round_2(moves));
+use crate::{AocError, Example, Solution};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    fn day(&self) -> usize {
+        2
+    }
+    fn title(&self) -> &'static str {
+        "Rock Paper Scissors"
+    }
+    fn input(&self) -> &'static str {
+        include_str!("input1.txt")
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: include_str!("example.txt"),
+            part1: Some("15"),
+            part2: Some("12"),
+        }]
+    }
+    fn labels(&self) -> [&'static str; 2] {
+        ["Guessed score", "Actual score"]
+    }
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok(rock_paper::play_round_1(input)?.to_string())
+    }
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok(rock_paper::play_round_2(input)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    crate::solution::example_tests!(super::Day02);
 }
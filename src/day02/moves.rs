@@ -2,8 +2,9 @@ use std::fmt;
 
 use super::outcome::Outcome;
 
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
 pub enum Move {
+    #[default]
     Undefined,
     Rock,
     Paper,
@@ -43,12 +44,6 @@ impl Move {
     }
 }
 
-impl Default for Move {
-    fn default() -> Self {
-        Self::Undefined
-    }
-}
-
 impl From<&str> for Move {
     fn from(other: &str) -> Self {
         match other.to_ascii_lowercase().as_str() {
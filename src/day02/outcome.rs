@@ -1,16 +1,12 @@
+#[derive(Default)]
 pub enum Outcome {
     Loss,
     Win,
     Draw,
+    #[default]
     Unplayed,
 }
 
-impl Default for Outcome {
-    fn default() -> Self {
-        Outcome::Unplayed
-    }
-}
-
 impl From<&str> for Outcome {
     fn from(other: &str) -> Self {
         match other.to_ascii_lowercase().as_str() {
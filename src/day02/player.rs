@@ -2,9 +2,11 @@ use std::fmt;
 
 use super::{moves::*, outcome::Outcome};
 
+#[derive(Default)]
 pub enum Player {
     Other(Move),
     Me(Move),
+    #[default]
     NoOne,
 }
 
@@ -28,12 +30,6 @@ impl Player {
     }
 }
 
-impl Default for Player {
-    fn default() -> Self {
-        Self::NoOne
-    }
-}
-
 impl fmt::Display for Player {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -1,25 +1,29 @@
 use super::moves::*;
+use super::outcome::Outcome;
 use super::player::*;
+use crate::AocError;
 
 const LOST_POINTS: usize = 0;
 const DRAW_POINTS: usize = 3;
 const WON_POINTS: usize = 6;
 
-pub fn play_round_1(moves: &str) -> usize {
-    moves
-        .split('\n')
-        .filter(|line| !line.trim().is_empty())
-        .map(extract_moves)
-        .map(|(other, me)| score_move(other, me))
-        .sum()
+/// The opponent's move and mine
+type Round = (Player, Player);
+
+pub fn play_round_1(moves: &str) -> Result<usize, AocError> {
+    rounds(moves, extract_moves)
+}
+
+pub fn play_round_2(moves: &str) -> Result<usize, AocError> {
+    rounds(moves, extract_new_rules_moves)
 }
 
-pub fn play_round_2(moves: &str) -> usize {
+fn rounds(moves: &str, extract: fn(usize, &str) -> Result<Round, AocError>) -> Result<usize, AocError> {
     moves
-        .split('\n')
-        .filter(|line| !line.trim().is_empty())
-        .map(extract_new_rules_moves)
-        .map(|(other, me)| score_move(other, me))
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| extract(index, line).map(|(other, me)| score_move(other, me)))
         .sum()
 }
 
@@ -38,27 +42,65 @@ fn score_move(other: Player, me: Player) -> usize {
     move_score + play_score
 }
 
-fn extract_moves(line: &str) -> (Player, Player) {
-    let mut other = Player::NoOne;
-    let mut me = Player::NoOne;
-    for (index, player_move) in line.trim().split(' ').into_iter().enumerate() {
-        let player_move = Move::from(player_move);
-        match index {
-            0 => other = Player::Other(player_move),
-            _ => me = Player::Me(player_move),
-        }
+/// The two symbols of a round, e.g. `["A", "Y"]`
+fn symbols(index: usize, line: &str) -> Result<[&str; 2], AocError> {
+    let symbols = line.split_whitespace().collect::<Vec<_>>();
+    match symbols[..] {
+        [other, me] => Ok([other, me]),
+        _ => Err(AocError::parse(index, line, line.trim_start(), format!("Expected two symbols, found {}", symbols.len()))),
     }
-    (other, me)
 }
 
-fn extract_new_rules_moves(line: &str) -> (Player, Player) {
-    let mut other = Move::Undefined;
-    let mut me = Move::Undefined;
-    for (index, symbol) in line.trim().split(' ').into_iter().enumerate() {
-        match index {
-            0 => other = Move::from(symbol),
-            _ => me = other.with_outcome(symbol),
-        }
+fn opponent_move(index: usize, line: &str, symbol: &str) -> Result<Move, AocError> {
+    match Move::from(symbol) {
+        Move::Undefined => Err(AocError::parse(index, line, symbol, format!("Unknown move `{symbol}`"))),
+        player_move => Ok(player_move),
+    }
+}
+
+fn extract_moves(index: usize, line: &str) -> Result<Round, AocError> {
+    let [other, me] = symbols(index, line)?;
+    Ok((Player::Other(opponent_move(index, line, other)?), Player::Me(opponent_move(index, line, me)?)))
+}
+
+fn extract_new_rules_moves(index: usize, line: &str) -> Result<Round, AocError> {
+    let [other, outcome] = symbols(index, line)?;
+    let other = opponent_move(index, line, other)?;
+    if matches!(Outcome::from(outcome), Outcome::Unplayed) {
+        return Err(AocError::parse(index, line, outcome, format!("Unknown outcome `{outcome}`")));
+    }
+    Ok((Player::Other(other), Player::Me(other.with_outcome(outcome))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("A Y\nB X\nC Z\n", Ok(15), Ok(12))]
+    #[case("A Y\r\n\r\nC Z", Ok(14), Ok(11))]
+    pub fn test_play(#[case] input: &str, #[case] round_1: Result<usize, AocError>, #[case] round_2: Result<usize, AocError>) {
+        assert_eq!(play_round_1(input), round_1);
+        assert_eq!(play_round_2(input), round_2);
+    }
+
+    #[rstest]
+    #[case("A Y\nA Q", (2, 3), "Unknown move `Q`")]
+    #[case("A Y\nD X", (2, 1), "Unknown move `D`")]
+    #[case("A Y\n  A", (2, 3), "Expected two symbols, found 1")]
+    #[case("A Y Z", (1, 1), "Expected two symbols, found 3")]
+    pub fn test_invalid_round(#[case] input: &str, #[case] position: (usize, usize), #[case] message: &str) {
+        let error = play_round_1(input).unwrap_err();
+        assert_eq!((error.line, error.column), (Some(position.0), Some(position.1)));
+        assert_eq!(error.message, message);
+    }
+
+    #[test]
+    pub fn test_invalid_outcome() {
+        let error = play_round_2("A Y\nB W").unwrap_err();
+        assert_eq!((error.line, error.column), (Some(2), Some(3)));
+        assert_eq!(error.message, "Unknown outcome `W`");
     }
-    (Player::Other(other), Player::Me(me))
 }
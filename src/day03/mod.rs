@@ -1,8 +1,38 @@
-mod rucksack;
+pub mod rucksack;
 
-pub fn run() {
-    let items = include_str!("input.txt");
-    println!("Day 3:");
-    println!("    Summed Priority: {}", rucksack::prioritize(items));
-    println!("    Badges Priority: {}", rucksack::find_badge_priority(items));
+use crate::{AocError, Example, Solution};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    fn day(&self) -> usize {
+        3
+    }
+    fn title(&self) -> &'static str {
+        "Rucksack Reorganization"
+    }
+    fn input(&self) -> &'static str {
+        include_str!("input.txt")
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: include_str!("example.txt"),
+            part1: Some("157"),
+            part2: Some("70"),
+        }]
+    }
+    fn labels(&self) -> [&'static str; 2] {
+        ["Summed Priority", "Badges Priority"]
+    }
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok(rucksack::prioritize(input)?.to_string())
+    }
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok(rucksack::find_badge_priority(input)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    crate::solution::example_tests!(super::Day03);
 }
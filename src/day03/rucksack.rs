@@ -1,41 +1,65 @@
+use crate::AocError;
+
 #[allow(non_camel_case_types)]
 type priority = usize;
 
-pub fn find_badge_priority(contents: &str) -> priority {
-    let mut chunk = vec![];
+pub fn find_badge_priority(contents: &str) -> Result<priority, AocError> {
+    let lines = rucksacks(contents)?;
     let mut badges_priorities = 0;
-    for (lineno, line) in contents.trim().split('\n').enumerate() {
-        chunk.push(line);
-        if lineno % 3 == 2 {
-            let possible_badges = intersection(chunk[0], chunk[1])
-                .into_iter()
-                .collect::<String>();
-            let team_badge = intersection(&possible_badges, chunk[2])
-                .into_iter()
-                .collect::<String>();
-            let team_priority = get_priority(&team_badge);
-            badges_priorities += team_priority;
-            chunk.clear();
+    for chunk in lines.chunks(3) {
+        let [(index, first), (_, second), (_, third)] = chunk else {
+            return Err(AocError::malformed(format!("{} rucksack(s) left over; elves are grouped in threes", chunk.len())));
+        };
+        let possible_badges = intersection(first, second)
+            .into_iter()
+            .collect::<String>();
+        let team_badge = intersection(&possible_badges, third)
+            .into_iter()
+            .collect::<String>();
+        if team_badge.is_empty() {
+            return Err(AocError::parse(*index, first, first, "Group has no common badge"));
+        }
+        badges_priorities += get_priority(&team_badge);
+    }
+    Ok(badges_priorities)
+}
+
+pub fn prioritize(contents: &str) -> Result<priority, AocError> {
+    let mut priorities = 0;
+    for (index, line) in rucksacks(contents)? {
+        let (compartment1, compartment2) = compartmentize(line);
+        let common_character = find_common_letter(compartment1, compartment2);
+        if common_character.is_empty() {
+            return Err(AocError::parse(index, line, line, "Compartments share no item"));
         }
+        priorities += get_priority(common_character);
     }
-    badges_priorities
+    Ok(priorities)
 }
 
-pub fn prioritize(contents: &str) -> priority {
+/// Non-empty lines with their 0-based index, checked to hold only item letters
+fn rucksacks(contents: &str) -> Result<Vec<(usize, &str)>, AocError> {
     contents
-        .split('\n')
-        .map(compartmentize)
-        .map(|(compartment1, compartment2)| find_common_letter(compartment1, compartment2))
-        .filter(|common_character| !common_character.is_empty())
-        .map(get_priority)
-        .sum()
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| {
+            match line
+                .char_indices()
+                .find(|(_, c)| !c.is_ascii_alphabetic())
+            {
+                Some((offset, c)) => Err(AocError::parse(index, line, &line[offset..], format!("`{c}` is not an item"))),
+                None => Ok((index, line)),
+            }
+        })
+        .collect()
 }
 
 pub fn find_common_letter<'a>(a: &'a str, b: &'a str) -> &'a str {
-    for (a_index, each_a) in a.chars().enumerate() {
-        for (_index, each_b) in b.chars().enumerate() {
+    for (a_index, each_a) in a.char_indices() {
+        for each_b in b.chars() {
             if each_a == each_b {
-                return &a[a_index..(a_index + 1)];
+                return &a[a_index..(a_index + each_a.len_utf8())];
             }
         }
     }
@@ -118,8 +142,8 @@ mod tests {
             "#,
         );
 
-        let actual = prioritize(&input.trim());
-        assert_eq!(actual, 157);
+        let actual = prioritize(input.trim());
+        assert_eq!(actual, Ok(157));
     }
 
     #[test]
@@ -135,7 +159,35 @@ mod tests {
             "#,
         );
 
-        let actual = find_badge_priority(&input.trim());
-        assert_eq!(actual, 70);
+        let actual = find_badge_priority(input.trim());
+        assert_eq!(actual, Ok(70));
+    }
+
+    #[rstest]
+    #[case("vJrwpWtwJgWrhcsFMMfFFhFp\nPmmdzq PrVvPw", (2, 7), "` ` is not an item")]
+    #[case("abcd\n\nab1d", (3, 3), "`1` is not an item")]
+    pub fn test_invalid_item(#[case] input: &str, #[case] position: (usize, usize), #[case] message: &str) {
+        let error = prioritize(input).unwrap_err();
+        assert_eq!((error.line, error.column), (Some(position.0), Some(position.1)));
+        assert_eq!(error.message, message);
+    }
+
+    #[rstest]
+    #[case("abab\nabcd", (2, 1))]
+    #[case("\nvJrwpWtwJgWrhcsFMMfFFhFp\nab", (3, 1))]
+    pub fn test_no_common_item(#[case] input: &str, #[case] position: (usize, usize)) {
+        let error = prioritize(input).unwrap_err();
+        assert_eq!(error.kind, crate::ErrorKind::Parse);
+        assert_eq!((error.line, error.column), (Some(position.0), Some(position.1)));
+        assert_eq!(error.message, "Compartments share no item");
+    }
+
+    #[rstest]
+    #[case("ab\nac\nad\nab", "1 rucksack(s) left over; elves are grouped in threes")]
+    #[case("ab\ncd\nef", "Group has no common badge")]
+    pub fn test_invalid_group(#[case] input: &str, #[case] message: &str) {
+        let error = find_badge_priority(input).unwrap_err();
+        assert_eq!(error.kind, crate::ErrorKind::Parse);
+        assert_eq!(error.message, message);
     }
 }
@@ -1,18 +1,21 @@
-pub fn count_fully_contained(pairs: &str) -> usize {
-    pairs
-        .lines()
-        .filter(ignore_empty)
-        .map(split_line)
-        .map(calculate_contained)
-        .sum()
+use crate::AocError;
+
+type Range = (usize, usize);
+
+pub fn count_fully_contained(pairs: &str) -> Result<usize, AocError> {
+    count(pairs, calculate_contained)
+}
+
+pub fn count_partial_overlap(pairs: &str) -> Result<usize, AocError> {
+    count(pairs, calculate_overlap)
 }
 
-pub fn count_partial_overlap(pairs: &str) -> usize {
+fn count(pairs: &str, calculate: fn([Range; 2]) -> usize) -> Result<usize, AocError> {
     pairs
         .lines()
-        .filter(ignore_empty)
-        .map(split_line)
-        .map(calculate_overlap)
+        .enumerate()
+        .filter(|(_, line)| ignore_empty(line))
+        .map(|(index, line)| parse_pair(index, line).map(calculate))
         .sum()
 }
 
@@ -20,31 +23,35 @@ pub fn ignore_empty(line: &&str) -> bool {
     !line.trim().is_empty()
 }
 
-pub fn split_line(line: &str) -> [&str; 2] {
-    let index = line
-        .chars()
-        .position(|c| c == ',')
-        .expect("Line is not valid");
-    [&line[..index], &line[index + 1..]]
+/// Both elves' section ranges from the 0-based `index` of the input's lines
+pub fn parse_pair(index: usize, line: &str) -> Result<[Range; 2], AocError> {
+    let [left, right] = split_line(index, line)?;
+    Ok([split_range(index, line, left)?, split_range(index, line, right)?])
 }
 
-pub fn split_range(elf_line: &str) -> (usize, usize) {
-    let index = elf_line
-        .chars()
-        .position(|c| c == '-')
-        .expect("Elf line is not valid");
-    let elf_min = &elf_line[..index]
-        .parse::<usize>()
-        .expect("Range is not valid");
-    let elf_max = &elf_line[index + 1..]
-        .parse::<usize>()
-        .expect("Range is not valid");
-    (*elf_min, *elf_max)
+pub fn split_line(index: usize, line: &str) -> Result<[&str; 2], AocError> {
+    let line = line.trim_end();
+    let comma = line
+        .find(',')
+        .ok_or_else(|| AocError::parse(index, line, line, "Line is not valid: expected two ranges separated by `,`"))?;
+    Ok([&line[..comma], &line[comma + 1..]])
 }
 
-pub fn calculate_overlap(elves: [&str; 2]) -> usize {
-    let (left_elf_min, left_elf_max) = split_range(elves[0]);
-    let (right_elf_min, right_elf_max) = split_range(elves[1]);
+/// Parses `elf_line`, a slice of `line`, as `min-max`
+pub fn split_range(index: usize, line: &str, elf_line: &str) -> Result<Range, AocError> {
+    let dash = elf_line
+        .find('-')
+        .ok_or_else(|| AocError::parse(index, line, elf_line, format!("Range `{elf_line}` is not valid: expected `min-max`")))?;
+    let bound = |value: &str| {
+        value
+            .parse::<usize>()
+            .map_err(|_| AocError::parse(index, line, value, format!("`{value}` is not a section number")))
+    };
+    Ok((bound(&elf_line[..dash])?, bound(&elf_line[dash + 1..])?))
+}
+
+pub fn calculate_overlap(elves: [Range; 2]) -> usize {
+    let [(left_elf_min, left_elf_max), (right_elf_min, right_elf_max)] = elves;
     let left_elf_range = left_elf_min..=left_elf_max;
     let right_elf_range = right_elf_min..=right_elf_max;
     if left_elf_range.contains(&right_elf_min) || left_elf_range.contains(&right_elf_max) || right_elf_range.contains(&left_elf_min) || right_elf_range.contains(&left_elf_max) {
@@ -53,9 +60,8 @@ pub fn calculate_overlap(elves: [&str; 2]) -> usize {
     0
 }
 
-pub fn calculate_contained(elves: [&str; 2]) -> usize {
-    let (left_elf_min, left_elf_max) = split_range(elves[0]);
-    let (right_elf_min, right_elf_max) = split_range(elves[1]);
+pub fn calculate_contained(elves: [Range; 2]) -> usize {
+    let [(left_elf_min, left_elf_max), (right_elf_min, right_elf_max)] = elves;
     let left_elf_range = left_elf_min..=left_elf_max;
     let right_elf_range = right_elf_min..=right_elf_max;
     if (left_elf_range.contains(&right_elf_min) && left_elf_range.contains(&right_elf_max)) || (right_elf_range.contains(&left_elf_min) && right_elf_range.contains(&left_elf_max)) {
@@ -84,7 +90,7 @@ mod tests {
     fn test_count_fully_contained(#[case] test_case: &str, #[case] expected: usize) {
         let input = textwrap::dedent(test_case);
         let actual = count_fully_contained(&input);
-        assert_eq!(actual, expected)
+        assert_eq!(actual, Ok(expected))
     }
 
     #[rstest]
@@ -95,8 +101,19 @@ mod tests {
     #[case("6-6,4-6", 1)]
     #[case("2-6,4-8", 0)]
     fn test_calculate_contained(#[case] test_case: &str, #[case] expected: usize) {
-        let elves = split_line(test_case);
+        let elves = parse_pair(0, test_case).unwrap();
         let actual = calculate_contained(elves);
         assert_eq!(actual, expected)
     }
+
+    #[rstest]
+    #[case("2-4,6-8\n2-4;6-8", (2, 1), "Line is not valid: expected two ranges separated by `,`")]
+    #[case("2-4,6_8", (1, 5), "Range `6_8` is not valid: expected `min-max`")]
+    #[case("2-4,6-x", (1, 7), "`x` is not a section number")]
+    #[case("-4,6-8", (1, 1), "`` is not a section number")]
+    fn test_invalid_pair(#[case] input: &str, #[case] position: (usize, usize), #[case] message: &str) {
+        let error = count_partial_overlap(input).unwrap_err();
+        assert_eq!((error.line, error.column), (Some(position.0), Some(position.1)));
+        assert_eq!(error.message, message);
+    }
 }
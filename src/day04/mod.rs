@@ -1,8 +1,38 @@
-mod camp_cleaning;
+pub mod camp_cleaning;
 
-pub fn run() {
-    let items = include_str!("input.txt");
-    println!("Day 4:");
-    println!("    Fully Overlap: {}", camp_cleaning::count_fully_contained(items));
-    println!("    Partial Overlap: {}", camp_cleaning::count_partial_overlap(items));
+use crate::{AocError, Example, Solution};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    fn day(&self) -> usize {
+        4
+    }
+    fn title(&self) -> &'static str {
+        "Camp Cleanup"
+    }
+    fn input(&self) -> &'static str {
+        include_str!("input.txt")
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: include_str!("example.txt"),
+            part1: Some("2"),
+            part2: Some("4"),
+        }]
+    }
+    fn labels(&self) -> [&'static str; 2] {
+        ["Fully Overlap", "Partial Overlap"]
+    }
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok(camp_cleaning::count_fully_contained(input)?.to_string())
+    }
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok(camp_cleaning::count_partial_overlap(input)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    crate::solution::example_tests!(super::Day04);
 }
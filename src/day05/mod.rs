@@ -1,8 +1,131 @@
-mod supply_stacks;
+pub mod supply_stacks;
 
-pub fn run() {
-    let items = include_str!("input.txt");
-    println!("Day 5:");
-    println!("    Crane 9000: {:?}", supply_stacks::arrange_crates_9000(items));
-    println!("    Crane 9001: {:?}", supply_stacks::arrange_crates_9001(items));
+use std::hint::black_box;
+
+use supply_stacks::{Machine, Move};
+
+use crate::{AocError, Example, Explore, Solution};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    fn day(&self) -> usize {
+        5
+    }
+    fn title(&self) -> &'static str {
+        "Supply Stacks"
+    }
+    fn input(&self) -> &'static str {
+        include_str!("input.txt")
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: include_str!("example.txt"),
+            part1: Some("CMZ"),
+            part2: Some("MCD"),
+        }]
+    }
+    fn labels(&self) -> [&'static str; 2] {
+        ["Crane 9000", "Crane 9001"]
+    }
+    fn has_parser(&self) -> bool {
+        true
+    }
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        black_box(supply_stacks::parse_stacks(input)?);
+        black_box(supply_stacks::parse_moves(input)?);
+        Ok(())
+    }
+    fn explore(&self, input: &str) -> Result<Option<Box<dyn Explore>>, AocError> {
+        let stacks = supply_stacks::parse_stacks(input)?;
+        Ok(Some(Box::new(Explorer {
+            stacks: stacks.clone(),
+            initial: stacks,
+            moves: supply_stacks::parse_moves(input)?,
+            next: 0,
+            crane: Machine::Crane9000,
+        })))
+    }
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok(supply_stacks::arrange_crates_9000(input)?.concat())
+    }
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok(supply_stacks::arrange_crates_9001(input)?.concat())
+    }
+}
+
+/// Steps through the rearrangement one move at a time
+struct Explorer {
+    initial: Vec<Vec<String>>,
+    stacks: Vec<Vec<String>>,
+    moves: Vec<Move>,
+    next: usize,
+    crane: fn(Move) -> Machine,
+}
+
+impl Explore for Explorer {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("show stacks", "Print each stack, bottom crate first"),
+            ("show moves", "List the moves not applied yet"),
+            ("step [n]", "Apply the next n moves (default 1)"),
+            ("set crane <9000|9001>", "Pick the crane used by step (default 9000)"),
+            ("reset", "Restore the starting stacks"),
+        ]
+    }
+
+    fn run(&mut self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        let output = match (command, args) {
+            ("show", ["stacks"]) => Ok(self
+                .stacks
+                .iter()
+                .enumerate()
+                .map(|(index, stack)| format!("{}: {}", index + 1, stack.join(" ")))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            ("show", ["moves"]) => Ok(self.moves[self.next..]
+                .iter()
+                .map(|movement| movement.to_string())
+                .collect::<Vec<_>>()
+                .join("\n")),
+            ("step", _) => {
+                let count = match args {
+                    [] => Ok(1),
+                    [count] => count
+                        .parse::<usize>()
+                        .map_err(|_| format!("`{count}` is not a number of moves")),
+                    _ => Err("Usage: step [n]".to_string()),
+                };
+                count.and_then(|count| {
+                    let end = (self.next + count).min(self.moves.len());
+                    while self.next < end {
+                        supply_stacks::execute_move(&(self.crane)(self.moves[self.next]), &mut self.stacks).map_err(|error| error.to_string())?;
+                        self.next += 1;
+                    }
+                    Ok(format!("Applied {} of {} moves", self.next, self.moves.len()))
+                })
+            }
+            ("set", ["crane", "9000"]) => {
+                self.crane = Machine::Crane9000;
+                Ok(String::new())
+            }
+            ("set", ["crane", "9001"]) => {
+                self.crane = Machine::Crane9001;
+                Ok(String::new())
+            }
+            ("set", ["crane", crane]) => Err(format!("Unknown crane `{crane}` (expected 9000 or 9001)")),
+            ("reset", []) => {
+                self.stacks = self.initial.clone();
+                self.next = 0;
+                Ok(String::new())
+            }
+            _ => return None,
+        };
+        Some(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    crate::solution::example_tests!(super::Day05);
 }
@@ -1,35 +1,48 @@
+use std::fmt;
+
+use tracing::info;
+
+use crate::AocError;
+
 pub enum Machine {
     Crane9000(Move),
     Crane9001(Move),
 }
 
-pub fn arrange_crates_9001(instructions: &str) -> Vec<String> {
-    let mut stacks = parse_stacks(instructions);
-    let movements = parse_moves(instructions)
+pub fn arrange_crates_9001(instructions: &str) -> Result<Vec<String>, AocError> {
+    let mut stacks = parse_stacks(instructions)?;
+    let movements = parse_moves(instructions)?
         .iter()
         .map(|movement| Machine::Crane9001(*movement))
         .collect::<Vec<_>>();
-    execute_moves(&movements, &mut stacks);
-    stacks
-        .iter()
-        .map(|stack| stack.clone().pop().unwrap())
-        .collect::<Vec<_>>()
+    execute_moves(&movements, &mut stacks)?;
+    top_crates(&stacks)
 }
 
-pub fn arrange_crates_9000(instructions: &str) -> Vec<String> {
-    let mut stacks = parse_stacks(instructions);
-    let movements = parse_moves(instructions)
+pub fn arrange_crates_9000(instructions: &str) -> Result<Vec<String>, AocError> {
+    let mut stacks = parse_stacks(instructions)?;
+    let movements = parse_moves(instructions)?
         .iter()
         .map(|movement| Machine::Crane9000(*movement))
         .collect::<Vec<_>>();
-    execute_moves(&movements, &mut stacks);
+    execute_moves(&movements, &mut stacks)?;
+    top_crates(&stacks)
+}
+
+fn top_crates(stacks: &[Vec<String>]) -> Result<Vec<String>, AocError> {
     stacks
         .iter()
-        .map(|stack| stack.clone().pop().unwrap())
-        .collect::<Vec<_>>()
+        .enumerate()
+        .map(|(index, stack)| {
+            stack
+                .last()
+                .cloned()
+                .ok_or_else(|| AocError::no_answer(format!("Stack {} ends up empty", index + 1)))
+        })
+        .collect()
 }
 
-pub fn parse_stacks(instructions: &str) -> Vec<Vec<String>> {
+pub fn parse_stacks(instructions: &str) -> Result<Vec<Vec<String>>, AocError> {
     let instructions = instructions
         .lines()
         .map(|s| s.to_string())
@@ -37,7 +50,7 @@ pub fn parse_stacks(instructions: &str) -> Vec<Vec<String>> {
     let end_of_stack_line_no = instructions
         .iter()
         .position(|line| line.trim().starts_with('1'))
-        .expect("Could not find end of stack lines");
+        .ok_or_else(|| AocError::malformed("Could not find the line numbering the stacks"))?;
     let mut stack_lines = instructions[..end_of_stack_line_no].to_vec();
     stack_lines.reverse();
     let mut stacks: Vec<Vec<String>> = vec![];
@@ -56,14 +69,14 @@ pub fn parse_stacks(instructions: &str) -> Vec<Vec<String>> {
                 }
             }
         });
-    stacks
+    Ok(stacks)
 }
 
 #[derive(Copy, Clone, Debug)]
 pub struct Move {
-    count: usize,
-    origination: usize,
-    destination: usize,
+    pub count: usize,
+    pub origination: usize,
+    pub destination: usize,
 }
 
 impl Move {
@@ -72,27 +85,57 @@ impl Move {
     }
 }
 
-pub fn parse_moves(instructions: &str) -> Vec<Move> {
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.count, self.origination, self.destination)
+    }
+}
+
+pub fn parse_moves(instructions: &str) -> Result<Vec<Move>, AocError> {
     instructions
         .lines()
-        .filter(|line| line.contains("move"))
-        .map(|line| {
-            let values = line
-                .split(' ')
-                .flat_map(|c| c.parse::<usize>())
-                .collect::<Vec<usize>>();
-            Move::new(values[0], values[1], values[2])
+        .enumerate()
+        .filter(|(_, line)| line.contains("move"))
+        .map(|(index, line)| {
+            let words = line.split_whitespace().collect::<Vec<_>>();
+            let ["move", count, "from", origination, "to", destination] = words[..] else {
+                return Err(AocError::parse(index, line, line.trim_start(), "Expected `move <n> from <stack> to <stack>`"));
+            };
+            let number = |value: &str| {
+                value
+                    .parse::<usize>()
+                    .map_err(|_| AocError::parse(index, line, value, format!("`{value}` is not a number")))
+            };
+            let stack = |value: &str| match number(value)? {
+                0 => Err(AocError::parse(index, line, value, "Stacks are numbered from 1")),
+                stack => Ok(stack),
+            };
+            Ok(Move::new(number(count)?, stack(origination)?, stack(destination)?))
         })
-        .collect::<Vec<_>>()
+        .collect()
 }
 
-pub fn execute_moves(movements: &[Machine], stacks: &mut [Vec<String>]) {
+pub fn execute_moves(movements: &[Machine], stacks: &mut [Vec<String>]) -> Result<(), AocError> {
     movements
         .iter()
-        .for_each(|movement| execute_move(movement, stacks))
+        .try_for_each(|movement| execute_move(movement, stacks))
 }
 
-pub fn execute_move(movement: &Machine, stacks: &mut [Vec<String>]) {
+pub fn execute_move(movement: &Machine, stacks: &mut [Vec<String>]) -> Result<(), AocError> {
+    let (Machine::Crane9000(inner) | Machine::Crane9001(inner)) = movement;
+    info!(target: "metrics", moves = 1, crates_moved = inner.count);
+    for stack in [inner.origination, inner.destination] {
+        if stack > stacks.len() {
+            return Err(AocError::malformed(format!("`{inner}` uses stack {stack}, but there are only {}", stacks.len())));
+        }
+    }
+    let available = stacks[inner.origination - 1].len();
+    if available < inner.count {
+        return Err(AocError::malformed(format!(
+            "`{inner}` needs {} crates, but stack {} holds {available}",
+            inner.count, inner.origination
+        )));
+    }
     match movement {
         Machine::Crane9000(movement) => (0..movement.count).for_each(|_move| {
             let item = stacks[movement.origination - 1]
@@ -114,4 +157,61 @@ pub fn execute_move(movement: &Machine, stacks: &mut [Vec<String>]) {
             stacks[movement.destination - 1].extend(items);
         }
     }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    const STACKS: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n";
+
+    #[test]
+    pub fn test_arrange_crates() {
+        let instructions = format!("{STACKS}move 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2\n");
+        assert_eq!(
+            arrange_crates_9000(&instructions)
+                .unwrap()
+                .concat(),
+            "CMZ"
+        );
+        assert_eq!(
+            arrange_crates_9001(&instructions)
+                .unwrap()
+                .concat(),
+            "MCD"
+        );
+    }
+
+    #[rstest]
+    #[case("move 1 from 2 to", (6, 1), "Expected `move <n> from <stack> to <stack>`")]
+    #[case("move one from 2 to 1", (6, 6), "`one` is not a number")]
+    #[case("move 1 from 0 to 1", (6, 13), "Stacks are numbered from 1")]
+    pub fn test_invalid_move(#[case] line: &str, #[case] position: (usize, usize), #[case] message: &str) {
+        let error = parse_moves(&format!("{STACKS}{line}\n")).unwrap_err();
+        assert_eq!((error.line, error.column), (Some(position.0), Some(position.1)));
+        assert_eq!(error.message, message);
+    }
+
+    #[rstest]
+    #[case("move 4 from 1 to 2", "`move 4 from 1 to 2` needs 4 crates, but stack 1 holds 2")]
+    #[case("move 1 from 1 to 4", "`move 1 from 1 to 4` uses stack 4, but there are only 3")]
+    pub fn test_impossible_move(#[case] line: &str, #[case] message: &str) {
+        let error = arrange_crates_9000(&format!("{STACKS}{line}\n")).unwrap_err();
+        assert_eq!(error.message, message);
+    }
+
+    #[test]
+    pub fn test_missing_stack_numbers() {
+        let error = parse_stacks("[A]\nmove 1 from 1 to 1\n").unwrap_err();
+        assert_eq!(error.kind, crate::ErrorKind::Parse);
+    }
+
+    #[test]
+    pub fn test_empty_stack() {
+        let error = arrange_crates_9000(&format!("{STACKS}move 1 from 3 to 1\n")).unwrap_err();
+        assert_eq!(error.message, "Stack 3 ends up empty");
+    }
 }
@@ -1,8 +1,60 @@
-mod tuning_trouble;
+pub mod tuning_trouble;
 
-pub fn run() {
-    let items = include_str!("input.txt");
-    println!("Day 6:");
-    println!("    Packet start: {:?}", tuning_trouble::detect_start(items, 4));
-    println!("    Message start: {:?}", tuning_trouble::detect_start(items, 14));
+use crate::{AocError, Example, Solution};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    fn day(&self) -> usize {
+        6
+    }
+    fn title(&self) -> &'static str {
+        "Tuning Trouble"
+    }
+    fn input(&self) -> &'static str {
+        include_str!("input.txt")
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                input: include_str!("example.txt"),
+                part1: Some("7"),
+                part2: Some("19"),
+            },
+            Example {
+                input: include_str!("example2.txt"),
+                part1: Some("5"),
+                part2: Some("23"),
+            },
+            Example {
+                input: include_str!("example3.txt"),
+                part1: Some("6"),
+                part2: Some("23"),
+            },
+            Example {
+                input: include_str!("example4.txt"),
+                part1: Some("10"),
+                part2: Some("29"),
+            },
+            Example {
+                input: include_str!("example5.txt"),
+                part1: Some("11"),
+                part2: Some("26"),
+            },
+        ]
+    }
+    fn labels(&self) -> [&'static str; 2] {
+        ["Packet start", "Message start"]
+    }
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok(tuning_trouble::detect_start(input, 4)?.to_string())
+    }
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok(tuning_trouble::detect_start(input, 14)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    crate::solution::example_tests!(super::Day06);
 }
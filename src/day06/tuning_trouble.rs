@@ -1,16 +1,28 @@
 use std::collections::HashSet;
 
-pub fn detect_start(data_stream: &str, packet_header_size: usize) -> usize {
+use crate::AocError;
+
+pub fn detect_start(data_stream: &str, packet_header_size: usize) -> Result<usize, AocError> {
+    if packet_header_size == 0 {
+        return Err(AocError::malformed("A marker is at least 1 character long"));
+    }
+    for (index, line) in data_stream.lines().enumerate() {
+        if let Some((offset, c)) = line.char_indices().find(|(_, c)| !c.is_ascii()) {
+            return Err(AocError::parse(index, line, &line[offset..], format!("`{c}` is not a signal character")));
+        }
+    }
+    // The line break after the stream is not a signal character
+    let data_stream = data_stream.trim_end_matches(['\r', '\n']);
     let packet_index = packet_header_size - 1;
-    for (i, _c) in data_stream.chars().enumerate() {
+    for (i, _c) in data_stream.char_indices() {
         if i < packet_index {
             continue;
         }
         if entry_is_unique(&data_stream[i - packet_index..=i]) {
-            return i + 1;
+            return Ok(i + 1);
         }
     }
-    0
+    Err(AocError::no_answer(format!("No {packet_header_size} distinct characters in a row")))
 }
 
 fn entry_is_unique(data_entry: &str) -> bool {
@@ -32,4 +44,17 @@ mod tests {
     fn test_entry_is_unique(#[case] input: &str, #[case] expected: bool) {
         assert_eq!(entry_is_unique(input), expected)
     }
+
+    #[rstest]
+    #[case("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4, Ok(7))]
+    #[case("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14, Ok(19))]
+    #[case("mjqjpqmgbljsphdztnvjfqwrcgsmlb\r\n", 4, Ok(7))]
+    #[case("aabb", 4, Err(crate::ErrorKind::NoAnswer))]
+    #[case("aabc\n", 4, Err(crate::ErrorKind::NoAnswer))]
+    #[case("abc\r\n", 4, Err(crate::ErrorKind::NoAnswer))]
+    #[case("abcdé", 4, Err(crate::ErrorKind::Parse))]
+    #[case("abc", 0, Err(crate::ErrorKind::Parse))]
+    fn test_detect_start(#[case] input: &str, #[case] size: usize, #[case] expected: Result<usize, crate::ErrorKind>) {
+        assert_eq!(detect_start(input, size).map_err(|error| error.kind), expected)
+    }
 }
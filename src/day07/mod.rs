@@ -1,33 +1,109 @@
-mod no_space;
-
-pub fn run(use_example: bool) {
-    #[allow(unused_assignments)]
-    let mut items = "";
-    if use_example {
-        items = include_str!("example.txt");
-    } else {
-        items = include_str!("input.txt");
-    }
-    println!("Day 7:");
-    println!("    Sum of folder sizes below 100k: {:?}", no_space::find_good_deletion_candidates(items));
-    println!("    Folder size to delete: {:?}", no_space::folder_to_delete(items));
+pub mod no_space;
+
+use std::{collections::HashMap, hint::black_box};
+
+use no_space::File;
+
+use crate::{table, AocError, Example, Explore, Solution};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    fn day(&self) -> usize {
+        7
+    }
+    fn title(&self) -> &'static str {
+        "No Space Left On Device"
+    }
+    fn input(&self) -> &'static str {
+        include_str!("input.txt")
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: include_str!("example.txt"),
+            part1: Some("95437"),
+            part2: Some("24933642"),
+        }]
+    }
+    fn labels(&self) -> [&'static str; 2] {
+        ["Sum of folder sizes below 100k", "Folder size to delete"]
+    }
+    fn has_parser(&self) -> bool {
+        true
+    }
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        black_box(no_space::build_sizes(input)?);
+        Ok(())
+    }
+    fn explore(&self, input: &str) -> Result<Option<Box<dyn Explore>>, AocError> {
+        Ok(Some(Box::new(Explorer { sizes: no_space::build_sizes(input)? })))
+    }
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok(no_space::find_good_deletion_candidates(input)?.to_string())
+    }
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok(no_space::folder_to_delete(input)?.to_string())
+    }
+}
+
+/// The sizes `build_sizes` worked out for every path
+struct Explorer {
+    sizes: HashMap<String, File>,
+}
+
+impl Explore for Explorer {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[("dump sizes [max]", "List every path and its size, or only directories up to max")]
+    }
+
+    fn run(&mut self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        let max = match (command, args) {
+            ("dump", ["sizes"]) => None,
+            ("dump", ["sizes", max]) => match max.parse::<usize>() {
+                Ok(max) => Some(max),
+                Err(_) => return Some(Err(format!("`{max}` is not a size"))),
+            },
+            _ => return None,
+        };
+        let mut rows = self
+            .sizes
+            .iter()
+            .filter(|(_, file)| match max {
+                Some(max) => matches!(file, File::Directory(_)) && file.size() <= max,
+                None => true,
+            })
+            .map(|(path, file)| {
+                let kind = match file {
+                    File::Directory(_) => "dir",
+                    File::File(_) => "file",
+                };
+                [path.clone(), kind.to_string(), file.size().to_string()]
+            })
+            .collect::<Vec<_>>();
+        rows.sort();
+        Some(Ok(table::render(["Path", "Kind", "Size"], &rows)
+            .trim_end()
+            .to_string()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::no_space::{find_good_deletion_candidates, folder_to_delete};
 
+    crate::solution::example_tests!(super::Day07);
+
     #[test]
     pub fn test_find_good_deletion_candidates() {
         let data = include_str!("example.txt");
         let actual = find_good_deletion_candidates(data);
-        assert_eq!(actual, 95_437);
+        assert_eq!(actual, Ok(95_437));
     }
 
     #[test]
     pub fn test_folder_to_delete() {
         let data = include_str!("example.txt");
         let actual = folder_to_delete(data);
-        assert_eq!(actual, 24_933_642);
+        assert_eq!(actual, Ok(24_933_642));
     }
 }
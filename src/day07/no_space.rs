@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use tracing::debug;
 
+use crate::AocError;
+
 type SomePath = String;
 
 #[derive(Clone, Debug)]
@@ -60,31 +62,31 @@ impl File {
     }
 }
 
-pub fn find_good_deletion_candidates(log: &str) -> usize {
-    folder_sizes_below(log, 100_000)
+pub fn find_good_deletion_candidates(log: &str) -> Result<usize, AocError> {
+    Ok(folder_sizes_below(log, 100_000)?
         .values()
         .map(|file| file.size())
-        .sum()
+        .sum())
 }
 
-pub fn folder_to_delete(log: &str) -> usize {
-    let used_space = largest_folder_size(log);
+pub fn folder_to_delete(log: &str) -> Result<usize, AocError> {
+    let used_space = largest_folder_size(log)?;
     let total_space_available = 70_000_000;
     let space_needed = 30_000_000;
-    let space_to_delete = used_space - (total_space_available - space_needed);
+    let space_to_delete = used_space.saturating_sub(total_space_available - space_needed);
     folder_sizes_above(log, space_to_delete)
 }
 
-pub fn largest_folder_size(log: &str) -> usize {
-    build_sizes(log)
+pub fn largest_folder_size(log: &str) -> Result<usize, AocError> {
+    build_sizes(log)?
         .values()
         .map(|v| v.size())
         .max()
-        .unwrap()
+        .ok_or_else(|| AocError::no_answer("The log lists no files"))
 }
 
-pub fn folder_sizes_above(log: &str, upper_threshold: usize) -> usize {
-    build_sizes(log)
+pub fn folder_sizes_above(log: &str, upper_threshold: usize) -> Result<usize, AocError> {
+    build_sizes(log)?
         .iter()
         .filter(|(_file_path, file)| {
             debug!("{_file_path}: {}", file.size());
@@ -92,24 +94,28 @@ pub fn folder_sizes_above(log: &str, upper_threshold: usize) -> usize {
         })
         .map(|(_file_path, file_data)| file_data.size())
         .min()
-        .unwrap()
+        .ok_or_else(|| AocError::no_answer(format!("No directory holds at least {upper_threshold}")))
 }
 
-pub fn folder_sizes_below(log: &str, upper_threshold: usize) -> HashMap<String, File> {
-    build_sizes(log)
+pub fn folder_sizes_below(log: &str, upper_threshold: usize) -> Result<HashMap<String, File>, AocError> {
+    Ok(build_sizes(log)?
         .iter()
         .inspect(|(fp, f)| debug!("Unfiltered - {fp}: {f:?}"))
         .filter(|(_file_path, file)| matches!(file, File::Directory(file_data) if file_data.size <= upper_threshold))
         .inspect(|(fp, f)| debug!("  Filtered - {fp}: {f:?}"))
         .map(|(fp, fd)| (fp.clone(), fd.clone()))
-        .collect()
+        .collect())
 }
 
-pub fn build_sizes(log: &str) -> HashMap<SomePath, File> {
+pub fn build_sizes(log: &str) -> Result<HashMap<SomePath, File>, AocError> {
     let mut cwd: Option<String> = None;
     let mut sizes = HashMap::new();
-    for line in log.split('\n').filter(|l| !l.trim().is_empty()) {
-        match tokenize(line) {
+    for (index, line) in log
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+    {
+        match tokenize(index, line)? {
             Stream::Input(Command::Change(path)) => {
                 match &cwd {
                     Some(s) => match s.as_str() {
@@ -122,7 +128,10 @@ pub fn build_sizes(log: &str) -> HashMap<SomePath, File> {
                 };
             }
             Stream::Input(Command::ChangeRelativeUp) => {
-                cwd = Some(dir(&cwd.clone().unwrap()).to_string());
+                let Some(path) = &cwd else {
+                    return Err(AocError::parse(index, line, line, "`cd ..` before entering any directory"));
+                };
+                cwd = Some(dir(path).to_string());
             }
             Stream::Input(Command::List) => {
                 // turns out this can just be ignored because no state is changed
@@ -174,7 +183,7 @@ pub fn build_sizes(log: &str) -> HashMap<SomePath, File> {
             }
         }
     }
-    sizes
+    Ok(sizes)
 }
 
 pub fn dir(path: &str) -> &str {
@@ -196,24 +205,52 @@ pub fn dir(path: &str) -> &str {
     }
 }
 
-pub fn tokenize(line: &str) -> Stream {
-    if let Some(line) = line.strip_prefix("$ cd ") {
-        if line == ".." {
+/// Reads one line of the terminal log, the 0-based `index` of the input's lines
+pub fn tokenize(index: usize, line: &str) -> Result<Stream, AocError> {
+    let stream = if let Some(path) = line.strip_prefix("$ cd ") {
+        if path == ".." {
             Stream::Input(Command::ChangeRelativeUp)
         } else {
-            Stream::Input(Command::Change(line.to_string()))
+            Stream::Input(Command::Change(path.to_string()))
         }
     } else if let Some(_line) = line.strip_prefix("$ ls") {
         Stream::Input(Command::List)
-    } else if let Some(line) = line.strip_prefix("dir ") {
-        Stream::Output(File::Directory(PathData::default().with_path(line).build()))
+    } else if let Some(command) = line.strip_prefix('$') {
+        return Err(AocError::parse(index, line, command.trim_start(), "Unknown command (expected `cd` or `ls`)"));
+    } else if let Some(path) = line.strip_prefix("dir ") {
+        Stream::Output(File::Directory(PathData::default().with_path(path).build()))
     } else {
         let (size_string, filename) = line
             .split_once(' ')
-            .expect("Could not parse file line");
+            .ok_or_else(|| AocError::parse(index, line, line, "Could not parse file line (expected `<size> <name>`)"))?;
         let size = size_string
             .parse::<usize>()
-            .expect("Could not parse file size");
+            .map_err(|_| AocError::parse(index, line, size_string, format!("`{size_string}` is not a file size")))?;
         Stream::Output(File::File(PathData::new(filename, size)))
+    };
+    Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("$ cd /\n$ ls\n14848514", (3, 1), "Could not parse file line (expected `<size> <name>`)")]
+    #[case("$ cd /\n$ ls\nabc b.txt", (3, 1), "`abc` is not a file size")]
+    #[case("$ cd /\n$ rm -rf a", (2, 3), "Unknown command (expected `cd` or `ls`)")]
+    #[case("$ cd ..", (1, 1), "`cd ..` before entering any directory")]
+    pub fn test_invalid_log(#[case] log: &str, #[case] position: (usize, usize), #[case] message: &str) {
+        let error = build_sizes(log).unwrap_err();
+        assert_eq!((error.line, error.column), (Some(position.0), Some(position.1)));
+        assert_eq!(error.message, message);
+    }
+
+    #[test]
+    pub fn test_empty_log() {
+        let error = folder_to_delete("$ cd /\n$ ls\n").unwrap_err();
+        assert_eq!(error.kind, crate::ErrorKind::NoAnswer);
     }
 }
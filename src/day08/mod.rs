@@ -1,33 +1,106 @@
-mod tree_top;
-
-pub fn run(use_example: bool) {
-    #[allow(unused_assignments)]
-    let mut items = "";
-    if use_example {
-        items = include_str!("example.txt");
-    } else {
-        items = include_str!("input.txt");
-    }
-    println!("Day 8:");
-    println!("    Trees visible: {:?}", tree_top::find_trees_visible(items));
-    println!("    Scenic score: {:?}", tree_top::find_highest_scenic_score(items));
+pub mod tree_top;
+
+use std::{collections::BTreeMap, hint::black_box};
+
+use tree_top::{Cell, Position};
+
+use crate::{AocError, Example, Explore, Solution};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    fn day(&self) -> usize {
+        8
+    }
+    fn title(&self) -> &'static str {
+        "Treetop Tree House"
+    }
+    fn input(&self) -> &'static str {
+        include_str!("input.txt")
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: include_str!("example.txt"),
+            part1: Some("21"),
+            part2: Some("8"),
+        }]
+    }
+    fn labels(&self) -> [&'static str; 2] {
+        ["Trees visible", "Scenic score"]
+    }
+    fn has_parser(&self) -> bool {
+        true
+    }
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        black_box(tree_top::parse_tree(input)?);
+        Ok(())
+    }
+    fn explore(&self, input: &str) -> Result<Option<Box<dyn Explore>>, AocError> {
+        Ok(Some(Box::new(Explorer { tree: tree_top::parse_tree(input)? })))
+    }
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok(tree_top::find_trees_visible(input)?.to_string())
+    }
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok(tree_top::find_highest_scenic_score(input)?.to_string())
+    }
+}
+
+/// The grid `parse_tree` builds, addressed by row and column
+struct Explorer {
+    tree: BTreeMap<Position, Cell>,
+}
+
+impl Explore for Explorer {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[("show tree", "Print the grid of tree heights"), ("score <row> <col>", "Scenic score of one tree (0-based)")]
+    }
+
+    fn run(&mut self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        let output = match (command, args) {
+            ("show", ["tree"]) => {
+                let mut grid = String::new();
+                for (position, cell) in self.tree.iter() {
+                    if position.x == 0 && position.y > 0 {
+                        grid.push('\n');
+                    }
+                    grid.push_str(&cell.value().to_string());
+                }
+                Ok(grid)
+            }
+            ("score", [row, col]) => match (row.parse::<i16>(), col.parse::<i16>()) {
+                (Ok(row), Ok(col)) => {
+                    let position = Position::new(row, col);
+                    match self.tree.contains_key(&position) {
+                        true => Ok(format!("Scenic score at ({row}, {col}): {}", tree_top::calculate_scenic_score(&position, &self.tree))),
+                        false => Err(format!("No tree at ({row}, {col})")),
+                    }
+                }
+                _ => Err("Usage: score <row> <col>".to_string()),
+            },
+            _ => return None,
+        };
+        Some(output)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::tree_top::*;
 
+    crate::solution::example_tests!(super::Day08);
+
     #[test]
     pub fn test_find_trees_visible() {
         let data = include_str!("example.txt");
         let actual = find_trees_visible(data);
-        assert_eq!(actual, 21);
+        assert_eq!(actual, Ok(21));
     }
 
     #[test]
     pub fn test_find_highest_scenic_score() {
         let data = include_str!("example.txt");
         let actual = find_highest_scenic_score(data);
-        assert_eq!(actual, 8);
+        assert_eq!(actual, Ok(8));
     }
 }
@@ -1,15 +1,18 @@
 use std::collections::BTreeMap;
 use std::fmt;
+use std::time::Instant;
 
-use tracing::{debug, trace};
+use tracing::{debug, info, trace};
 
-pub fn find_highest_scenic_score(tree: &str) -> usize {
-    let tree = parse_tree(tree);
+use crate::AocError;
+
+pub fn find_highest_scenic_score(tree: &str) -> Result<usize, AocError> {
+    let tree = parse_tree(tree)?;
     tree.iter()
         .filter(|(_pos, cell)| matches!(cell, Cell::Value(_)))
         .map(|(pos, _cell)| calculate_scenic_score(pos, &tree))
         .max()
-        .unwrap()
+        .ok_or_else(|| AocError::no_answer("Every tree is on the edge"))
 }
 
 pub fn calculate_scenic_score(position: &Position, tree: &BTreeMap<Position, Cell>) -> usize {
@@ -38,8 +41,8 @@ pub fn calculate_scenic_score(position: &Position, tree: &BTreeMap<Position, Cel
     score
 }
 
-pub fn find_trees_visible(tree: &str) -> usize {
-    let tree = parse_tree(tree);
+pub fn find_trees_visible(tree: &str) -> Result<usize, AocError> {
+    let tree = parse_tree(tree)?;
     let directions = [Direction::North, Direction::South, Direction::East, Direction::West];
     let visible_edges = tree
         .iter()
@@ -65,7 +68,7 @@ pub fn find_trees_visible(tree: &str) -> usize {
             visible
         })
         .count();
-    visible_internal + visible_edges
+    Ok(visible_internal + visible_edges)
 }
 
 pub fn cast_ray(start: &Position, direction: Direction, tree: &BTreeMap<Position, Cell>) -> Vec<Position> {
@@ -81,6 +84,7 @@ pub fn cast_ray(start: &Position, direction: Direction, tree: &BTreeMap<Position
             None => break,
         }
     }
+    info!(target: "metrics", rays = 1, ray_cells = ray.len() - 1);
     ray
 }
 
@@ -155,23 +159,31 @@ impl<X: Into<i16>, Y: Into<i16>> From<(Y, X)> for Position {
     }
 }
 
-pub fn parse_tree(tree: &str) -> BTreeMap<Position, Cell> {
+pub fn parse_tree(tree: &str) -> Result<BTreeMap<Position, Cell>, AocError> {
+    let start = Instant::now();
     let mut map = BTreeMap::new();
-    let line_count = tree
-        .split('\n')
-        .map(|l| l.trim())
-        .filter(|l| !l.is_empty())
-        .count() as i16;
-    for (y, line) in tree.split('\n').enumerate() {
+    let lines = tree
+        .lines()
+        .enumerate()
+        .map(|(index, raw)| (index, raw, raw.trim()))
+        .filter(|(_, _, l)| !l.is_empty())
+        .collect::<Vec<_>>();
+    let line_count = lines.len() as i16;
+    let width = lines
+        .first()
+        .map_or(0, |(_, _, line)| line.chars().count());
+    for (y, (index, raw, line)) in lines.into_iter().enumerate() {
         let y = y as i16;
         let col_count = line.chars().count() as i16;
-        for (x, c) in line.chars().enumerate() {
+        if col_count as usize != width {
+            return Err(AocError::parse(index, raw, line, format!("Row has {col_count} trees, but the first row has {width}")));
+        }
+        for ((x, c), (offset, _)) in line.chars().enumerate().zip(line.char_indices()) {
             let x = x as i16;
             let pos = Position::new(y, x);
             let v = c
-                .to_string()
-                .parse::<u8>()
-                .expect("Could not parse value");
+                .to_digit(10)
+                .ok_or_else(|| AocError::parse(index, raw, &line[offset..], format!("`{c}` is not a tree height")))? as u8;
             let cell = match (x, y) {
                 (x, y) if x == 0 || y == 0 || x == col_count - 1 || y == line_count - 1 => Cell::Edge(v),
                 _ => Cell::Value(v),
@@ -179,7 +191,8 @@ pub fn parse_tree(tree: &str) -> BTreeMap<Position, Cell> {
             map.insert(pos, cell);
         }
     }
-    map
+    info!(target: "metrics", parse_time = start.elapsed().as_secs_f64());
+    Ok(map)
 }
 
 #[cfg(test)]
@@ -195,16 +208,20 @@ mod tests {
         ((0, 0), Cell::Edge(3)), ((0, 1), Cell::Edge(2)), ((0, 2), Cell::Edge(1)),
         ((1, 0), Cell::Edge(4)), ((1, 1), Cell::Value(5)), ((1, 2), Cell::Edge(6)),
         ((2, 0), Cell::Edge(7)), ((2, 1), Cell::Edge(8)), ((2, 2), Cell::Edge(9))])]
+    #[case("32\r\n10\r\n", &[
+        ((0, 0), Cell::Edge(3)), ((0, 1), Cell::Edge(2)),
+        ((1, 0), Cell::Edge(1)), ((1, 1), Cell::Edge(0))])]
     pub fn test_parse_tree(#[case] tree: &str, #[case] expected: &[((i16, i16), Cell)]) {
         let expected = expected
             .iter()
             .map(|(p, c)| {
-                let p: Position = p.clone().into();
-                let c: Cell = c.clone().into();
+                let p: Position = (*p).into();
+                let c: Cell = *c;
                 (p, c)
             })
             .collect::<BTreeMap<Position, Cell>>();
         let actual = parse_tree(tree)
+            .unwrap()
             .iter()
             .map(|(p, c)| (*p, *c))
             .collect::<BTreeMap<_, _>>();
@@ -214,7 +231,7 @@ mod tests {
     #[rstest]
     #[case("3210", (0, 0), Direction::East, &[(0, 0), (0, 1), (0, 2), (0, 3)])]
     pub fn test_cast_array(#[case] tree: &str, #[case] start: (i16, i16), #[case] direction: Direction, #[case] expected: &[(i16, i16)]) {
-        let tree = parse_tree(tree);
+        let tree = parse_tree(tree).unwrap();
         let expected = expected
             .iter()
             .map(|(y, x)| Position::new(*y, *x))
@@ -223,4 +240,20 @@ mod tests {
         let actual = cast_ray(&start, direction, &tree);
         assert_eq!(actual, expected)
     }
+
+    #[rstest]
+    #[case("123\n4a6\n789", (2, 2), "`a` is not a tree height")]
+    #[case("123\n\n  45\n", (3, 3), "Row has 2 trees, but the first row has 3")]
+    pub fn test_invalid_tree(#[case] tree: &str, #[case] position: (usize, usize), #[case] message: &str) {
+        let error = parse_tree(tree).unwrap_err();
+        assert_eq!((error.line, error.column), (Some(position.0), Some(position.1)));
+        assert_eq!(error.message, message);
+    }
+
+    #[test]
+    pub fn test_no_interior() {
+        assert_eq!(find_trees_visible("12\n34"), Ok(4));
+        let error = find_highest_scenic_score("12\n34").unwrap_err();
+        assert_eq!(error.kind, crate::ErrorKind::NoAnswer);
+    }
 }
@@ -1,16 +1,94 @@
-mod rope_bridge;
-
-pub fn run(use_example: bool) {
-    #[allow(unused_assignments)]
-    let mut items = "";
-    if use_example {
-        items = include_str!("example.txt");
-    } else {
-        items = include_str!("input.txt");
-    }
-    println!("Day 9:");
-    println!("    Tail Position Count(1): {:?}", rope_bridge::count_tail_position(items, 2));
-    println!("    Tail Position Count(10): {:?}", rope_bridge::count_tail_position(items, 10));
+pub mod rope_bridge;
+
+use std::hint::black_box;
+
+use crate::{AocError, Example, Explore, Solution};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    fn day(&self) -> usize {
+        9
+    }
+    fn title(&self) -> &'static str {
+        "Rope Bridge"
+    }
+    fn input(&self) -> &'static str {
+        include_str!("input.txt")
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                input: include_str!("example.txt"),
+                part1: Some("13"),
+                part2: Some("1"),
+            },
+            Example {
+                input: include_str!("example2.txt"),
+                part1: Some("88"),
+                part2: Some("36"),
+            },
+        ]
+    }
+    fn labels(&self) -> [&'static str; 2] {
+        ["Tail Position Count(1)", "Tail Position Count(10)"]
+    }
+    fn has_parser(&self) -> bool {
+        true
+    }
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        black_box(rope_bridge::parse_moves(input)?);
+        Ok(())
+    }
+    fn explore(&self, input: &str) -> Result<Option<Box<dyn Explore>>, AocError> {
+        rope_bridge::parse_moves(input)?;
+        Ok(Some(Box::new(Explorer { input: input.to_string(), knots: 2 })))
+    }
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok(rope_bridge::count_tail_position(input, 2)?.to_string())
+    }
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok(rope_bridge::count_tail_position(input, 10)?.to_string())
+    }
+}
+
+/// Simulates the rope with any number of knots; `explore` has checked the moves parse
+struct Explorer {
+    input: String,
+    knots: usize,
+}
+
+impl Explore for Explorer {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("set knots <n>", "Number of knots in the rope, head included (default 2)"),
+            ("tail", "Count the positions the tail visits"),
+            ("show moves", "List the parsed head moves"),
+        ]
+    }
+
+    fn run(&mut self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        let output = match (command, args) {
+            ("set", ["knots", knots]) => match knots.parse::<usize>() {
+                Ok(knots) => {
+                    self.knots = knots;
+                    Ok(String::new())
+                }
+                Err(_) => Err(format!("`{knots}` is not a number of knots")),
+            },
+            ("tail", []) => rope_bridge::count_tail_position(&self.input, self.knots)
+                .map(|count| format!("Tail visits {count} positions with {} knots", self.knots))
+                .map_err(|error| error.to_string()),
+            ("show", ["moves"]) => Ok(rope_bridge::parse_moves(&self.input)
+                .unwrap_or_default()
+                .iter()
+                .map(|update| update.to_string())
+                .collect::<Vec<_>>()
+                .join("\n")),
+            _ => return None,
+        };
+        Some(output)
+    }
 }
 
 #[cfg(test)]
@@ -18,6 +96,8 @@ mod tests {
     use super::rope_bridge::*;
     use rstest::rstest;
 
+    crate::solution::example_tests!(super::Day09);
+
     #[rstest]
     #[case::simple_tail_example1("example1", 2, 13)]
     #[case::simple_tail_example2("example2", 2, 88)]
@@ -32,6 +112,22 @@ mod tests {
             _ => unreachable!(),
         };
         let actual = count_tail_position(data_set, knots);
-        assert_eq!(actual, expected);
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn test_count_tail_position_without_knots() {
+        let error = count_tail_position(include_str!("example.txt"), 0).unwrap_err();
+        assert_eq!(error.kind, crate::ErrorKind::Parse);
+    }
+
+    #[rstest]
+    #[case("R 4\nQ 4", (2, 1), "Unknown direction `Q` (expected U, D, L or R)")]
+    #[case("R 4\nU -1", (2, 3), "`-1` is not a number of steps")]
+    #[case("R4", (1, 1), "Expected `<direction> <steps>`")]
+    fn test_invalid_moves(#[case] moves: &str, #[case] position: (usize, usize), #[case] message: &str) {
+        let error = parse_moves(moves).unwrap_err();
+        assert_eq!((error.line, error.column), (Some(position.0), Some(position.1)));
+        assert_eq!(error.message, message);
     }
 }
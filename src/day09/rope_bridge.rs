@@ -1,6 +1,8 @@
 use std::{collections::HashSet, fmt, ops};
 
-use tracing::{debug, trace};
+use tracing::{debug, info, trace};
+
+use crate::AocError;
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Position {
@@ -42,8 +44,8 @@ impl ops::Add<Update> for Position {
 
 #[derive(Clone, Copy, Debug, Default)]
 pub struct Update {
-    direction: Direction,
-    distance: usize,
+    pub direction: Direction,
+    pub distance: usize,
 }
 
 impl Update {
@@ -58,11 +60,15 @@ impl fmt::Display for Update {
     }
 }
 
-pub fn count_tail_position(moves: &str, knot_count: usize) -> usize {
+pub fn count_tail_position(moves: &str, knot_count: usize) -> Result<usize, AocError> {
+    if knot_count == 0 {
+        return Err(AocError::malformed("The rope needs at least 1 knot"));
+    }
     let mut positions: Vec<Position> = vec![Position::default(); knot_count];
     let mut tail_positions = vec![Position::default()];
+    let mut knot_updates = 0;
 
-    parse_moves(moves)
+    parse_moves(moves)?
         .iter()
         .enumerate()
         .for_each(|(move_id, head_move)| {
@@ -89,6 +95,7 @@ pub fn count_tail_position(moves: &str, knot_count: usize) -> usize {
                     break;
                 }
                 *position = *position + next_update;
+                knot_updates += 1;
                 debug!("{move_id}:{position_id}: Update: {position} **updated**");
                 last_position = *position;
                 if position_id == knot_count - 1 {
@@ -98,11 +105,12 @@ pub fn count_tail_position(moves: &str, knot_count: usize) -> usize {
             }
             debug!("Positions: {:?}", positions);
         });
+    info!(target: "metrics", knot_updates, tail_moves = tail_positions.len() - 1);
     let steps = tail_positions
         .iter()
         .copied()
         .collect::<HashSet<Position>>();
-    steps.len()
+    Ok(steps.len())
 }
 
 pub fn calculate_direction(head_pos: &Position, tail_pos: &Position) -> Direction {
@@ -130,7 +138,7 @@ pub fn is_touching(pos1: &Position, pos2: &Position) -> bool {
     (pos1.x - 1..=pos1.x + 1).contains(&pos2.x) && (pos1.y - 1..=pos1.y + 1).contains(&pos2.y)
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Direction {
     Up,
     Down,
@@ -140,6 +148,7 @@ pub enum Direction {
     UpLeft,
     DownRight,
     DownLeft,
+    #[default]
     Stationary,
 }
 
@@ -149,12 +158,6 @@ impl fmt::Display for Direction {
     }
 }
 
-impl Default for Direction {
-    fn default() -> Self {
-        Self::Stationary
-    }
-}
-
 impl From<&str> for Direction {
     fn from(other: &str) -> Self {
         match other.to_ascii_lowercase().as_str() {
@@ -167,16 +170,26 @@ impl From<&str> for Direction {
     }
 }
 
-pub fn parse_moves(positions: &str) -> Vec<Update> {
-    positions
-        .split('\n')
-        .filter(|line| !line.trim().is_empty())
-        .flat_map(|line| {
-            let (direction, size) = line.split_once(' ').unwrap();
-            let direction = Direction::from(direction);
-            let size = size.parse::<usize>().unwrap();
-            (0..size).map(move |_| Update::new(direction, 1))
-        })
-        .inspect(|u| trace!("Move: {u}"))
-        .collect::<Vec<_>>()
+pub fn parse_moves(positions: &str) -> Result<Vec<Update>, AocError> {
+    let mut updates = Vec::new();
+    for (index, line) in positions
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+    {
+        let (direction, size) = line
+            .split_once(' ')
+            .ok_or_else(|| AocError::parse(index, line, line, "Expected `<direction> <steps>`"))?;
+        let direction = match Direction::from(direction) {
+            Direction::Stationary => return Err(AocError::parse(index, line, direction, format!("Unknown direction `{direction}` (expected U, D, L or R)"))),
+            direction => direction,
+        };
+        let size = size
+            .trim_end()
+            .parse::<usize>()
+            .map_err(|_| AocError::parse(index, line, size, format!("`{size}` is not a number of steps")))?;
+        updates.extend((0..size).map(|_| Update::new(direction, 1)));
+    }
+    updates.iter().for_each(|u| trace!("Move: {u}"));
+    Ok(updates)
 }
@@ -0,0 +1,188 @@
+use std::{error, fmt};
+
+/// What went wrong, which also decides the process exit code
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The input could not be read at all
+    Input,
+    /// The input is malformed
+    Parse,
+    /// The input parsed, but has no answer (e.g. no start-of-packet marker)
+    NoAnswer,
+    /// The solver panicked
+    Panic,
+}
+
+impl ErrorKind {
+    /// Exit codes 0-2 are taken by success, failed answers and usage errors
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Input => 3,
+            Self::Parse => 4,
+            Self::NoAnswer => 5,
+            Self::Panic => 6,
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Input => write!(f, "input error"),
+            Self::Parse => write!(f, "parse error"),
+            Self::NoAnswer => write!(f, "no answer"),
+            Self::Panic => write!(f, "solver panicked"),
+        }
+    }
+}
+
+/// A failure to solve a day, pointing at the offending input where possible
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AocError {
+    pub kind: ErrorKind,
+    /// Filled in by `Solution::solve`, since solvers do not know their day
+    pub day: Option<usize>,
+    /// 1-based line number
+    pub line: Option<usize>,
+    /// 1-based column, in characters
+    pub column: Option<usize>,
+    /// The offending line
+    pub text: Option<String>,
+    pub message: String,
+}
+
+impl AocError {
+    fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            day: None,
+            line: None,
+            column: None,
+            text: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn input(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Input, message)
+    }
+
+    /// Malformed input at the 0-based `index` of `input.lines()`, pointing at
+    /// `token`, which should be a slice of `line`
+    pub fn parse(index: usize, line: &str, token: &str, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(index + 1),
+            column: Some(column(line, token)),
+            text: Some(line.to_string()),
+            ..Self::new(ErrorKind::Parse, message)
+        }
+    }
+
+    /// Malformed input that cannot be pinned to a line, e.g. a missing section
+    pub fn malformed(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Parse, message)
+    }
+
+    pub fn no_answer(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::NoAnswer, message)
+    }
+
+    pub fn panic(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Panic, message)
+    }
+
+    pub fn in_day(mut self, day: usize) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    /// A multi-line report in the style of compiler errors:
+    ///
+    /// ```text
+    /// error: unknown move `Q`
+    ///  --> day 2, line 3, column 3
+    ///   |
+    /// 3 | A Q
+    ///   |   ^
+    /// ```
+    pub fn diagnostic(&self) -> String {
+        let mut report = format!("error: {}", self.message);
+        let location = [
+            self.day.map(|day| format!("day {day}")),
+            self.line.map(|line| format!("line {line}")),
+            self.column
+                .map(|column| format!("column {column}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        if !location.is_empty() {
+            report.push_str(&format!("\n --> {}", location.join(", ")));
+        }
+        if let (Some(line), Some(text)) = (self.line, &self.text) {
+            let gutter = " ".repeat(line.to_string().len());
+            report.push_str(&format!("\n{gutter} |\n{line} | {text}"));
+            if let Some(column) = self.column {
+                report.push_str(&format!("\n{gutter} | {}^", " ".repeat(column - 1)));
+            }
+        }
+        report
+    }
+}
+
+/// 1-based character column of `token` within `line`; falls back to the first
+/// occurrence when `token` is not a slice of `line`
+pub fn column(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let offset = (token.as_ptr() as usize)
+        .checked_sub(start)
+        .filter(|offset| offset + token.len() <= line.len())
+        .or_else(|| line.find(token))
+        .unwrap_or_default();
+    line[..offset].chars().count() + 1
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {line}, column {column}: {}", self.message),
+            (Some(line), None) => write!(f, "line {line}: {}", self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl error::Error for AocError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("A Q", 2, 3)]
+    #[case("A Q", 0, 1)]
+    #[case("é Q", 3, 3)]
+    pub fn test_column_of_slice(#[case] line: &str, #[case] offset: usize, #[case] expected: usize) {
+        assert_eq!(column(line, &line[offset..]), expected);
+    }
+
+    #[test]
+    pub fn test_column_of_copy() {
+        assert_eq!(column("A Q", "Q"), 3);
+        assert_eq!(column("A Q", "Z"), 1);
+    }
+
+    #[test]
+    pub fn test_diagnostic() {
+        let line = "A Q";
+        let error = AocError::parse(2, line, &line[2..], "unknown move `Q`").in_day(2);
+        assert_eq!(error.to_string(), "line 3, column 3: unknown move `Q`");
+        assert_eq!(error.diagnostic(), "error: unknown move `Q`\n --> day 2, line 3, column 3\n  |\n3 | A Q\n  |   ^");
+        assert_eq!(error.kind.exit_code(), 4);
+
+        let error = AocError::no_answer("no marker found");
+        assert_eq!(error.diagnostic(), "error: no marker found");
+    }
+}
@@ -0,0 +1,460 @@
+//! Seeded generators of valid puzzle inputs, for stress-testing the solvers
+//! on more than the committed input and examples.
+//!
+//! The same day, size and seed always produce the same input.
+
+use std::collections::HashSet;
+
+/// A small, deterministic pseudo-random generator (SplitMix64).  Not for
+/// anything but test data; its output never changes between releases, so
+/// seeds stay reproducible.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `low..=high`
+    pub fn range(&mut self, low: usize, high: usize) -> usize {
+        assert!(low <= high, "empty range {low}..={high}");
+        let span = (high - low) as u64 + 1;
+        low + (self.next_u64() % span) as usize
+    }
+
+    /// True with probability `numerator / denominator`
+    pub fn chance(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.range(1, denominator) <= numerator
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.range(0, items.len() - 1)]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.range(0, index));
+        }
+    }
+}
+
+/// A day's input generator
+pub struct Generator {
+    pub day: usize,
+    /// What `size` counts, e.g. "elves"
+    pub unit: &'static str,
+    /// Roughly the scale of a real puzzle input
+    pub default_size: usize,
+    pub generate: fn(&mut Rng, usize) -> String,
+}
+
+pub static GENERATORS: &[Generator] = &[
+    Generator {
+        day: 1,
+        unit: "elves",
+        default_size: 250,
+        generate: calories,
+    },
+    Generator {
+        day: 2,
+        unit: "rounds",
+        default_size: 2500,
+        generate: strategy_guide,
+    },
+    Generator {
+        day: 3,
+        unit: "groups of three elves",
+        default_size: 100,
+        generate: rucksacks,
+    },
+    Generator {
+        day: 4,
+        unit: "pairs",
+        default_size: 1000,
+        generate: section_pairs,
+    },
+    Generator {
+        day: 5,
+        unit: "moves",
+        default_size: 500,
+        generate: crate_stacks,
+    },
+    Generator {
+        day: 6,
+        unit: "characters",
+        default_size: 4096,
+        generate: datastream,
+    },
+    Generator {
+        day: 7,
+        unit: "directories",
+        default_size: 200,
+        generate: terminal,
+    },
+    Generator {
+        day: 8,
+        unit: "trees per side",
+        default_size: 99,
+        generate: tree_grid,
+    },
+    Generator {
+        day: 9,
+        unit: "moves",
+        default_size: 2000,
+        generate: rope_moves,
+    },
+];
+
+pub fn find(day: usize) -> Option<&'static Generator> {
+    GENERATORS
+        .iter()
+        .find(|generator| generator.day == day)
+}
+
+/// An input for `day` at `size` from `seed`, or `None` if the day has no generator
+pub fn generate(day: usize, size: usize, seed: u64) -> Option<String> {
+    let generator = find(day)?;
+    Some((generator.generate)(&mut Rng::new(seed), size))
+}
+
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const LETTERS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Day 1: blank-line separated blocks of calorie counts, one block per elf
+pub fn calories(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            (0..rng.range(1, 15))
+                .map(|_| format!("{}\n", rng.range(1000, 60000)))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Day 2: one `A X` line per round
+pub fn strategy_guide(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{} {}\n", rng.pick(&['A', 'B', 'C']), rng.pick(&['X', 'Y', 'Z'])))
+        .collect()
+}
+
+/// Day 3: groups of three rucksacks, where each rucksack has exactly one item
+/// type in both compartments and each group has exactly one item type in all
+/// three rucksacks
+pub fn rucksacks(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size.max(1) {
+        let mut letters = LETTERS.chars().collect::<Vec<_>>();
+        rng.shuffle(&mut letters);
+        let badge = letters.pop().unwrap();
+        // Disjoint pools, so only the badge can be in all three rucksacks
+        for pool in letters.chunks(letters.len() / 3) {
+            let mut pool = pool.to_vec();
+            let shared = match rng.chance(1, 4) {
+                true => badge,
+                false => pool.pop().unwrap(),
+            };
+            let (left_pool, right_pool) = pool.split_at(pool.len() / 2);
+            let length = rng.range(2, 20);
+            let mut left = (1..length)
+                .map(|_| rng.pick(left_pool))
+                .collect::<Vec<_>>();
+            let mut right = (1..length)
+                .map(|_| rng.pick(right_pool))
+                .collect::<Vec<_>>();
+            left.push(shared);
+            right.push(shared);
+            if shared != badge {
+                // On one side only, or it would be a second shared item
+                let side = match rng.chance(1, 2) {
+                    true => &mut left,
+                    false => &mut right,
+                };
+                // Not the last item, which is the shared one
+                let index = rng.range(0, side.len() - 2);
+                side[index] = badge;
+            }
+            rng.shuffle(&mut left);
+            rng.shuffle(&mut right);
+            output.extend(left.iter().chain(right.iter()));
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// Day 4: one `a-b,c-d` line per pair of elves
+pub fn section_pairs(rng: &mut Rng, size: usize) -> String {
+    let range = |rng: &mut Rng| {
+        let start = rng.range(1, 99);
+        (start, rng.range(start, 99))
+    };
+    (0..size.max(1))
+        .map(|_| {
+            let ((a, b), (c, d)) = (range(rng), range(rng));
+            format!("{a}-{b},{c}-{d}\n")
+        })
+        .collect()
+}
+
+/// Day 5: a drawing of up to nine stacks, then `size` moves that never take
+/// more crates than a stack holds and never empty one
+pub fn crate_stacks(rng: &mut Rng, size: usize) -> String {
+    let count = rng.range(3, 9);
+    let mut heights = (0..count)
+        .map(|_| rng.range(2, 8))
+        .collect::<Vec<_>>();
+    let tallest = *heights.iter().max().unwrap();
+    let mut output = String::new();
+    for level in (0..tallest).rev() {
+        let row = heights
+            .iter()
+            .map(|height| match level < *height {
+                true => format!("[{}]", rng.pick(&LETTERS.as_bytes()[26..]) as char),
+                false => "   ".to_string(),
+            })
+            .collect::<Vec<_>>();
+        output.push_str(&row.join(" "));
+        output.push('\n');
+    }
+    let numbers = (1..=count)
+        .map(|number| format!(" {number} "))
+        .collect::<Vec<_>>();
+    output.push_str(&numbers.join(" "));
+    output.push_str("\n\n");
+    for _ in 0..size {
+        // Every stack starts with two crates and keeps at least one, so one can always give
+        let givers = (0..count)
+            .filter(|stack| heights[*stack] > 1)
+            .collect::<Vec<_>>();
+        let from = rng.pick(&givers);
+        let to = (from + rng.range(1, count - 1)) % count;
+        let moved = rng.range(1, heights[from] - 1);
+        heights[from] -= moved;
+        heights[to] += moved;
+        output.push_str(&format!("move {moved} from {} to {}\n", from + 1, to + 1));
+    }
+    output
+}
+
+/// Day 6: noise that can never hold a start-of-message marker, with fourteen
+/// distinct characters placed somewhere in it
+pub fn datastream(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(14);
+    let mut letters = LOWERCASE.chars().collect::<Vec<_>>();
+    rng.shuffle(&mut letters);
+    let noise = letters[..rng.range(3, 13)].to_vec();
+    rng.shuffle(&mut letters);
+    let start = rng.range(0, size - 14);
+    let mut stream = (0..size)
+        .map(|_| rng.pick(&noise))
+        .collect::<Vec<_>>();
+    stream[start..start + 14].copy_from_slice(&letters[..14]);
+    stream.into_iter().collect::<String>() + "\n"
+}
+
+/// A name not yet `taken` in its directory; only files get extensions
+fn name(rng: &mut Rng, taken: &mut HashSet<String>, file: bool) -> String {
+    loop {
+        let mut name = (0..rng.range(1, 8))
+            .map(|_| rng.pick(LOWERCASE.as_bytes()) as char)
+            .collect::<String>();
+        if file && rng.chance(1, 2) {
+            name.push('.');
+            name.push_str(rng.pick(&["txt", "dat", "log", "lst"]));
+        }
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+/// Day 7: the transcript of exploring `size` directories depth first, with
+/// `cd ..` back up after each
+pub fn terminal(rng: &mut Rng, size: usize) -> String {
+    struct Directory {
+        name: String,
+        children: Vec<usize>,
+        files: Vec<(String, usize)>,
+    }
+    let mut directories = vec![Directory {
+        name: "/".to_string(),
+        children: vec![],
+        files: vec![],
+    }];
+    let mut names = vec![HashSet::new()];
+    for index in 1..size.max(1) {
+        // Favouring recent directories makes the tree deeper
+        let parent = match rng.chance(2, 3) {
+            true => rng.range(index.saturating_sub(3), index - 1),
+            false => rng.range(0, index - 1),
+        };
+        let name = name(rng, &mut names[parent], false);
+        directories[parent].children.push(index);
+        directories.push(Directory {
+            name,
+            children: vec![],
+            files: vec![],
+        });
+        names.push(HashSet::new());
+    }
+    for (index, directory) in directories.iter_mut().enumerate() {
+        let minimum = usize::from(index == 0);
+        for _ in 0..rng.range(minimum, 5) {
+            let name = name(rng, &mut names[index], true);
+            directory
+                .files
+                .push((name, rng.range(1, 200_000)));
+        }
+    }
+    let mut output = String::from("$ cd /\n");
+    let mut stack = vec![(0, false)];
+    while let Some((index, listed)) = stack.pop() {
+        let directory = &directories[index];
+        if listed {
+            if index != 0 {
+                output.push_str("$ cd ..\n");
+            }
+            continue;
+        }
+        if index != 0 {
+            output.push_str(&format!("$ cd {}\n", directory.name));
+        }
+        output.push_str("$ ls\n");
+        let mut entries = directory
+            .children
+            .iter()
+            .map(|child| format!("dir {}\n", directories[*child].name))
+            .chain(
+                directory
+                    .files
+                    .iter()
+                    .map(|(name, size)| format!("{size} {name}\n")),
+            )
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut entries);
+        output.extend(entries);
+        stack.push((index, true));
+        stack.extend(
+            directory
+                .children
+                .iter()
+                .rev()
+                .map(|child| (*child, false)),
+        );
+    }
+    output
+}
+
+/// Day 8: a square grid of tree heights, at least 3 wide so it has an interior
+pub fn tree_grid(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(3);
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| char::from(b'0' + rng.range(0, 9) as u8))
+                .chain(Some('\n'))
+                .collect::<String>()
+        })
+        .collect()
+}
+
+/// Day 9: one `R 4` line per head move
+pub fn rope_moves(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{} {}\n", rng.pick(&['U', 'D', 'L', 'R']), rng.range(1, 20)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Part, SOLUTIONS};
+
+    use rstest::rstest;
+
+    #[test]
+    pub fn test_every_day_has_a_generator() {
+        // Scaffolded days get a generator once they are solved
+        for solution in SOLUTIONS
+            .iter()
+            .filter(|solution| solution.solved())
+        {
+            assert!(find(solution.day()).is_some(), "day {}", solution.day());
+        }
+    }
+
+    #[rstest]
+    pub fn test_inputs_solve(#[values(1, 2, 3, 4, 5, 6, 7, 8, 9)] day: usize, #[values(1, 20)] size: usize) {
+        let solution = crate::find(day).unwrap();
+        for seed in 0..5 {
+            let input = generate(day, size, seed).unwrap();
+            for part in [Part::One, Part::Two] {
+                let answer = solution.solve(part, &input);
+                assert!(answer.is_ok(), "day {day} part {part} seed {seed}: {answer:?}\n{input}");
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_seeds_are_reproducible() {
+        assert_eq!(generate(7, 10, 42), generate(7, 10, 42));
+        assert_ne!(generate(7, 10, 42), generate(7, 10, 43));
+        assert_eq!(Rng::new(0).next_u64(), 0xE220_A839_7B1D_CDAF);
+    }
+
+    #[test]
+    pub fn test_rucksack_groups() {
+        let input = rucksacks(&mut Rng::new(3), 50);
+        let items = |line: &str| line.chars().collect::<HashSet<_>>();
+        let lines = input.lines().collect::<Vec<_>>();
+        for line in lines.iter() {
+            let (left, right) = line.split_at(line.len() / 2);
+            assert_eq!(items(left).intersection(&items(right)).count(), 1, "{line}");
+        }
+        for group in lines.chunks(3) {
+            let common = items(group[0])
+                .intersection(&items(group[1]))
+                .copied()
+                .collect::<HashSet<_>>();
+            assert_eq!(common.intersection(&items(group[2])).count(), 1, "{group:?}");
+        }
+    }
+
+    #[test]
+    pub fn test_sizes() {
+        assert_eq!(
+            calories(&mut Rng::new(1), 4)
+                .split("\n\n")
+                .count(),
+            4
+        );
+        assert_eq!(rucksacks(&mut Rng::new(1), 4).lines().count(), 12);
+        assert_eq!(
+            crate_stacks(&mut Rng::new(1), 4)
+                .matches("move")
+                .count(),
+            4
+        );
+        assert_eq!(datastream(&mut Rng::new(1), 100).trim_end().len(), 100);
+        assert_eq!(
+            terminal(&mut Rng::new(1), 4)
+                .matches("dir ")
+                .count(),
+            3
+        );
+        assert_eq!(tree_grid(&mut Rng::new(1), 5).lines().count(), 5);
+    }
+}
@@ -0,0 +1,121 @@
+use std::{
+    borrow::Cow,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use crate::Solution;
+
+/// Where a day's puzzle input comes from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Source {
+    /// The `input.txt` compiled into the day's module
+    #[default]
+    Embedded,
+    /// The Nth (1-based) example compiled into the day's module:
+    /// `example.txt`, `example2.txt`, ...
+    Example(usize),
+    /// A file read at runtime
+    File(PathBuf),
+    /// Standard input, read to the end
+    Stdin,
+}
+
+impl From<PathBuf> for Source {
+    fn from(path: PathBuf) -> Self {
+        match path.to_str() {
+            Some("-") => Self::Stdin,
+            _ => Self::File(path),
+        }
+    }
+}
+
+impl Source {
+    pub fn read(&self, solution: &dyn Solution) -> io::Result<Cow<'static, str>> {
+        let text = match self {
+            Self::Embedded => Cow::Borrowed(solution.input()),
+            Self::Example(number) => match solution.example(*number) {
+                Some(example) => Cow::Borrowed(example),
+                None => {
+                    let message = format!("Day {} has {} example(s), not {number}", solution.day(), solution.examples().len());
+                    return Err(io::Error::new(io::ErrorKind::NotFound, message));
+                }
+            },
+            Self::File(path) => Cow::Owned(fs::read_to_string(path)?),
+            Self::Stdin => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text)?;
+                Cow::Owned(text)
+            }
+        };
+        Ok(normalize(text))
+    }
+}
+
+/// Converts CRLF line endings to LF and guarantees a trailing newline
+pub fn normalize(text: Cow<'_, str>) -> Cow<'_, str> {
+    if !text.contains('\r') && (text.is_empty() || text.ends_with('\n')) {
+        return text;
+    }
+    let mut text = text.replace("\r\n", "\n");
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    Cow::Owned(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::find;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("", "")]
+    #[case("a\nb\n", "a\nb\n")]
+    #[case("a\nb", "a\nb\n")]
+    #[case("a\r\nb\r\n", "a\nb\n")]
+    #[case("a\r\n\r\nb", "a\n\nb\n")]
+    pub fn test_normalize(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(normalize(Cow::Borrowed(input)), expected);
+    }
+
+    #[rstest]
+    #[case("-", Source::Stdin)]
+    #[case("input.txt", Source::File(PathBuf::from("input.txt")))]
+    pub fn test_source_from_path(#[case] path: &str, #[case] expected: Source) {
+        assert_eq!(Source::from(PathBuf::from(path)), expected);
+    }
+
+    #[test]
+    pub fn test_read_file() {
+        let solution = find(4).unwrap();
+        let path = env::temp_dir().join("aoc-2022-test-read-file.txt");
+        fs::write(&path, "2-4,6-8\r\n2-8,3-7").unwrap();
+        let input = Source::File(path.clone()).read(solution).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(solution.part1(&input).as_deref(), Ok("1"));
+    }
+
+    #[rstest]
+    #[case(1, Some("mjqjpqmgbljsphdztnvjfqwrcgsmlb\n"))]
+    #[case(5, Some("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw\n"))]
+    #[case(0, None)]
+    #[case(6, None)]
+    pub fn test_read_example(#[case] number: usize, #[case] expected: Option<&str>) {
+        let solution = find(6).unwrap();
+        let input = Source::Example(number).read(solution).ok();
+        assert_eq!(input.as_deref(), expected);
+    }
+
+    #[test]
+    pub fn test_read_missing_file() {
+        let solution = find(4).unwrap();
+        let source = Source::File(PathBuf::from("does/not/exist.txt"));
+        assert!(source.read(solution).is_err());
+    }
+}
@@ -1,3 +1,8 @@
+pub mod alloc;
+pub mod answers;
+pub mod bench;
+pub mod calendar;
+pub mod client;
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -7,3 +12,68 @@ pub mod day06;
 pub mod day07;
 pub mod day08;
 pub mod day09;
+pub mod error;
+pub mod generate;
+pub mod input;
+pub mod logging;
+pub mod metrics;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(test)]
+mod reference;
+pub mod repl;
+pub mod runner;
+pub mod scaffold;
+pub mod server;
+pub mod solution;
+mod table;
+pub mod watch;
+
+pub use error::{AocError, ErrorKind};
+pub use solution::{Example, Explore, Part, Parts, Solution};
+
+/// Every implemented day, in order.  New days register here.
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+];
+
+pub fn find(day: usize) -> Option<&'static dyn Solution> {
+    SOLUTIONS
+        .iter()
+        .copied()
+        .find(|solution| solution.day() == day)
+}
+
+pub fn latest() -> &'static dyn Solution {
+    *SOLUTIONS.last().expect("No solutions registered")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_solutions_are_ordered() {
+        let days = SOLUTIONS
+            .iter()
+            .map(|solution| solution.day())
+            .collect::<Vec<_>>();
+        let expected = (1..=SOLUTIONS.len()).collect::<Vec<_>>();
+        assert_eq!(days, expected);
+    }
+
+    #[test]
+    pub fn test_find() {
+        assert_eq!(find(4).map(|solution| solution.day()), Some(4));
+        assert!(find(0).is_none());
+        assert_eq!(latest().day(), SOLUTIONS.len());
+    }
+}
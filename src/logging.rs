@@ -0,0 +1,162 @@
+use std::{fs::File, path::PathBuf, str::FromStr, sync::Arc};
+
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::{filter, fmt, prelude::*, EnvFilter, Layer, Registry};
+
+use crate::metrics::{self, Metrics};
+
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// How log lines are written
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Pretty,
+    Json,
+}
+
+impl std::fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pretty => write!(f, "pretty"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "pretty" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Invalid log format: `{s}` (expected pretty or json)")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogConfig {
+    /// Most verbose level shown on the console
+    pub level: LevelFilter,
+    /// File receiving everything `RUST_LOG` (or `level`) lets through; `None` disables it
+    pub file: Option<PathBuf>,
+    pub format: LogFormat,
+    /// Collect `metrics` events for an end-of-run summary
+    pub metrics: bool,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            level: LevelFilter::INFO,
+            file: None,
+            format: LogFormat::Pretty,
+            metrics: false,
+        }
+    }
+}
+
+/// Maps `-v`/`-q` counts onto a level, starting from INFO
+pub fn verbosity(verbose: u8, quiet: u8) -> LevelFilter {
+    const LEVELS: [LevelFilter; 6] = [LevelFilter::OFF, LevelFilter::ERROR, LevelFilter::WARN, LevelFilter::INFO, LevelFilter::DEBUG, LevelFilter::TRACE];
+    let index = 3 + verbose as isize - quiet as isize;
+    LEVELS[index.clamp(0, LEVELS.len() as isize - 1) as usize]
+}
+
+fn format_layer<W>(format: LogFormat, writer: W) -> BoxedLayer
+where
+    W: for<'writer> fmt::MakeWriter<'writer> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Pretty => fmt::layer().pretty().with_writer(writer).boxed(),
+        LogFormat::Json => fmt::layer().json().with_writer(writer).boxed(),
+    }
+}
+
+/// Installs the global subscriber, returning the metrics it collects
+pub fn init(config: &LogConfig) -> Metrics {
+    // Console logs go to stderr so they never mix with answers on stdout
+    let console_log = format_layer(config.format, std::io::stderr).with_filter(config.level);
+
+    // A layer that logs events to a file.
+    let debug_log = config
+        .file
+        .as_ref()
+        .and_then(|path| match File::create(path) {
+            Ok(file) => Some(format_layer(config.format, Arc::new(file))),
+            Err(error) => {
+                eprintln!("Could not create log file {}: {error}. File logging is disabled.", path.display());
+                None
+            }
+        });
+
+    // A layer that collects metrics using specific events.
+    let metrics_layer = Metrics::default();
+
+    // `RUST_LOG` wins when set; otherwise everything up to the console level is recorded.
+    // Metrics are switched on or off as a whole, independent of the log level.
+    let metrics_level = match config.metrics {
+        true => LevelFilter::TRACE,
+        false => LevelFilter::OFF,
+    };
+    let env_filter = EnvFilter::builder()
+        .with_default_directive(config.level.into())
+        .from_env_lossy()
+        .add_directive(
+            format!("{}={metrics_level}", metrics::TARGET)
+                .parse()
+                .unwrap(),
+        );
+
+    tracing_subscriber::registry()
+        .with(
+            console_log
+                // Combine the filtered console layer with the
+                // `debug_log` layer, producing a new `Layered` layer.
+                .and_then(debug_log)
+                // Add a filter to *both* layers that rejects spans and
+                // events whose targets start with `metrics`.
+                .with_filter(filter::filter_fn(|metadata| !metadata.target().starts_with("metrics"))),
+        )
+        .with(
+            // Add a filter to the metrics label that *only* enables
+            // events whose targets start with `metrics`.
+            metrics_layer
+                .clone()
+                .with_filter(filter::filter_fn(|metadata| metadata.target().starts_with("metrics"))),
+        )
+        .with(env_filter)
+        .init();
+    metrics_layer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, 0, LevelFilter::INFO)]
+    #[case(1, 0, LevelFilter::DEBUG)]
+    #[case(2, 0, LevelFilter::TRACE)]
+    #[case(5, 0, LevelFilter::TRACE)]
+    #[case(0, 1, LevelFilter::WARN)]
+    #[case(0, 2, LevelFilter::ERROR)]
+    #[case(0, 3, LevelFilter::OFF)]
+    #[case(0, 9, LevelFilter::OFF)]
+    #[case(1, 1, LevelFilter::INFO)]
+    pub fn test_verbosity(#[case] verbose: u8, #[case] quiet: u8, #[case] expected: LevelFilter) {
+        assert_eq!(verbosity(verbose, quiet), expected);
+    }
+
+    #[rstest]
+    #[case("pretty", Ok(LogFormat::Pretty))]
+    #[case("JSON", Ok(LogFormat::Json))]
+    #[case("xml", Err("Invalid log format: `xml` (expected pretty or json)".to_string()))]
+    pub fn test_parse_log_format(#[case] input: &str, #[case] expected: Result<LogFormat, String>) {
+        assert_eq!(input.parse::<LogFormat>(), expected);
+    }
+}
@@ -1,10 +1,28 @@
-use std::{fs::File, sync::Arc};
+use std::{
+    borrow::Cow,
+    io,
+    path::{Path, PathBuf},
+    process, thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
-use chrono::{Duration, Local, NaiveDate};
-use clap::Parser;
-use tracing_subscriber::{filter, prelude::*, EnvFilter};
+use chrono::{Local, Utc};
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use serde_json::json;
 
-use aoc_2022::*;
+use aoc_2022::{
+    alloc::AllocStats,
+    answers::Answers,
+    input::Source,
+    logging::{self, LogConfig, LogFormat},
+    metrics::Metrics,
+    watch::Watcher,
+    *,
+};
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: alloc::Counting = alloc::Counting;
 
 #[derive(Parser)]
 pub struct Cli {
@@ -12,78 +30,537 @@ pub struct Cli {
     #[arg(short, long, env = "DAY")]
     day: Option<usize>,
 
-    /// Use Example flag
-    #[arg(short, long)]
-    example: bool,
+    /// Use the day's Nth example instead of its input (default 1)
+    #[arg(short, long, value_name = "N", num_args = 0..=1, default_missing_value = "1")]
+    example: Option<usize>,
+
+    /// Puzzle input file, or `-` for stdin; defaults to the embedded input
+    #[arg(short, long, value_name = "PATH", conflicts_with = "example")]
+    input: Option<PathBuf>,
+
+    /// Puzzle input file, or `-` for stdin (same as --input)
+    #[arg(value_name = "INPUT", conflicts_with_all = ["example", "input"])]
+    positional_input: Option<PathBuf>,
+
+    /// Part to run: 1, 2 or both
+    #[arg(short, long, default_value_t = Parts::Both)]
+    part: Parts,
+
+    /// Run every implemented day and print a summary table
+    #[arg(short, long, conflicts_with_all = ["day", "input", "positional_input"])]
+    all: bool,
+
+    /// Time parsing and each part over N runs (default 10) instead of printing answers
+    #[arg(short, long, value_name = "N", num_args = 0..=1, default_missing_value = "10")]
+    bench: Option<usize>,
+
+    /// Re-run the day whenever the --input file changes, comparing against the previous run
+    #[arg(short, long, conflicts_with_all = ["all", "bench", "example"])]
+    watch: bool,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Run days and parts on N threads (default: one per core); output order is unchanged
+    #[arg(short, long, value_name = "N", num_args = 0..=1, default_missing_value = "0", conflicts_with_all = ["bench", "watch"])]
+    jobs: Option<usize>,
+
+    /// Count allocations, bytes and peak live bytes per day and part (needs the `alloc-stats` feature)
+    // The counters are process wide, so only the sequential runner can attribute them
+    #[arg(long, conflicts_with_all = ["bench", "watch", "jobs"])]
+    alloc_stats: bool,
+
+    /// Print a summary of solver metrics (counters and timings) at the end of the run
+    // Benchmark iterations run outside the per-part `solve` span, so their metrics would be unattributed
+    #[arg(long, env = "AOC_METRICS", conflicts_with = "bench")]
+    metrics: bool,
+
+    /// Log more; repeat for more detail (-v debug, -vv trace)
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Log less; repeat to silence (-q warn, -qq error, -qqq off)
+    #[arg(short, long, action = ArgAction::Count, global = true, conflicts_with = "verbose")]
+    quiet: u8,
+
+    /// Also write log output to this file
+    #[arg(long, value_name = "PATH", env = "AOC_LOG_FILE", global = true)]
+    log_file: Option<PathBuf>,
+
+    /// Log line format: pretty or json
+    #[arg(long, env = "AOC_LOG_FORMAT", default_value_t = LogFormat::Pretty, global = true)]
+    log_format: LogFormat,
+
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-fn init_logging() {
-    let stdout_log = tracing_subscriber::fmt::layer().pretty();
+#[derive(Subcommand)]
+enum Command {
+    /// Run every day on its input and compare against the stored answers
+    Verify {
+        /// Stored answers file
+        #[arg(long, env = "AOC_ANSWERS", default_value = "answers.toml")]
+        answers: PathBuf,
+    },
+    /// Generate and register a `src/dayNN` module skeleton
+    New {
+        /// Day of the event
+        day: usize,
+        /// Solver module name, e.g. `cathode-ray`
+        #[arg(short, long)]
+        name: String,
+        /// Crate root to generate into
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+    /// Download a day's puzzle input into the local cache (never twice)
+    Fetch {
+        /// Day of the event
+        day: usize,
+        /// Advent of Code server
+        #[arg(long, env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL)]
+        base_url: String,
+    },
+    /// Solve one part, submit the answer and record the verdict in the answers file
+    Submit {
+        /// Day of the event
+        day: usize,
+        /// Part to submit: 1 or 2
+        part: Part,
+        /// Puzzle input file, or `-` for stdin; defaults to the embedded input
+        #[arg(short, long, value_name = "PATH")]
+        input: Option<PathBuf>,
+        /// Stored answers file
+        #[arg(long, env = "AOC_ANSWERS", default_value = "answers.toml")]
+        answers: PathBuf,
+        /// Advent of Code server
+        #[arg(long, env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL)]
+        base_url: String,
+    },
+    /// Load a day's input and explore its parsed data interactively
+    Repl {
+        /// Day of the event
+        day: usize,
+        /// Puzzle input file; defaults to the embedded input
+        #[arg(short, long, value_name = "PATH", conflicts_with = "example")]
+        input: Option<PathBuf>,
+        /// Load the day's Nth example instead (default 1)
+        #[arg(short, long, value_name = "N", num_args = 0..=1, default_missing_value = "1")]
+        example: Option<usize>,
+    },
+    /// Print a random, valid input for a day, e.g. to stress-test a solver
+    Generate {
+        /// Day of the event
+        day: usize,
+        /// Scale of the input, e.g. elves for day 1 or moves for day 9; defaults to a real input's scale
+        #[arg(short, long)]
+        size: Option<usize>,
+        /// Seed for a reproducible input; a random one is picked and printed to stderr otherwise
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Serve the solvers over HTTP: `GET /days` and `POST /days/{day}/parts/{part}` with the input as body
+    Serve {
+        /// Port to listen on
+        #[arg(short, long, env = "AOC_PORT", default_value_t = 8022)]
+        port: u16,
+        /// Address to bind; use 0.0.0.0 to accept connections from other machines
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        /// Connections served at once
+        #[arg(long, default_value_t = aoc_2022::server::DEFAULT_WORKERS)]
+        workers: usize,
+    },
+}
 
-    // A layer that logs events to a file.
-    let file = File::create("debug.log");
-    let file = match file {
-        Ok(file) => file,
-        Err(error) => panic!("Error: {error:?}"),
-    };
-    let debug_log = tracing_subscriber::fmt::layer().with_writer(Arc::new(file));
-
-    // A layer that collects metrics using specific events.
-    let metrics_layer = /* ... */ filter::LevelFilter::INFO;
-
-    tracing_subscriber::registry()
-        .with(
-            stdout_log
-                // Add an `INFO` filter to the stdout logging layer
-                .with_filter(filter::LevelFilter::INFO)
-                // Combine the filtered `stdout_log` layer with the
-                // `debug_log` layer, producing a new `Layered` layer.
-                .and_then(debug_log)
-                // Add a filter to *both* layers that rejects spans and
-                // events whose targets start with `metrics`.
-                .with_filter(filter::filter_fn(|metadata| !metadata.target().starts_with("metrics"))),
-        )
-        .with(
-            // Add a filter to the metrics label that *only* enables
-            // events whose targets start with `metrics`.
-            metrics_layer.with_filter(filter::filter_fn(|metadata| metadata.target().starts_with("metrics"))),
-        )
-        .with(EnvFilter::from_default_env())
-        .init();
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// Human readable lines and tables
+    Text,
+    /// One JSON object per line, per day and part (or benchmark phase)
+    Json,
 }
 
 fn main() {
     let args = Cli::parse();
-    init_logging();
-    let day = {
-        if let Some(day) = args.day {
-            day as i64
-        } else {
-            let start = NaiveDate::from_ymd_opt(2022, 12, 1)
-                .unwrap()
-                .and_hms_milli_opt(0, 0, 0, 0)
-                .unwrap()
-                .and_local_timezone(Local)
-                .unwrap();
-            let today = Local::now();
-            let diff = today - start + Duration::days(1);
-            diff.num_days()
+    let metrics = logging::init(&LogConfig {
+        level: logging::verbosity(args.verbose, args.quiet),
+        file: args.log_file.clone(),
+        format: args.log_format,
+        metrics: args.metrics,
+    });
+    match &args.command {
+        Some(Command::Verify { answers }) => return verify(answers, args.format),
+        Some(Command::New { day, name, root }) => return new_day(root, *day, name),
+        Some(Command::Fetch { day, base_url }) => return fetch(base_url, *day),
+        Some(Command::Submit { day, part, input, answers, base_url }) => return submit(base_url, answers, *day, *part, input.clone()),
+        Some(Command::Repl { day, input, example }) => return repl(*day, input.clone(), *example),
+        Some(Command::Generate { day, size, seed }) => return generate(*day, *size, *seed),
+        Some(Command::Serve { port, host, workers }) => return serve(host, *port, *workers),
+        None => {}
+    }
+    if args.watch {
+        return watch(&args);
+    }
+    if args.alloc_stats && !alloc::enabled() {
+        eprintln!("--alloc-stats needs a build with the allocation counter: cargo run --features alloc-stats -- ...");
+        process::exit(2);
+    }
+    let jobs = match args.all {
+        true => {
+            let source = match args.example {
+                Some(number) => Source::Example(number),
+                None => Source::Embedded,
+            };
+            SOLUTIONS
+                .iter()
+                // Only embedded inputs are read here, so the one failure is a day with fewer examples
+                .filter_map(|solution| match source.read(*solution) {
+                    Ok(input) => Some((*solution, input)),
+                    Err(error) => {
+                        eprintln!("Skipping day {}: {error}", solution.day());
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        }
+        false => vec![single_day(&args)],
+    };
+
+    if let Some(iterations) = args.bench {
+        let timings = jobs
+            .iter()
+            .flat_map(|(solution, input)| bench::bench(*solution, args.part, input, iterations))
+            .collect::<Vec<_>>();
+        match args.format {
+            Format::Text => print!("{}", bench::table(&timings)),
+            Format::Json => timings
+                .iter()
+                .for_each(|timing| println!("{}", timing.to_json())),
+        }
+        return;
+    }
+
+    let tasks = jobs
+        .iter()
+        .flat_map(|(solution, input)| {
+            args.part
+                .iter()
+                .map(move |part| (*solution, part, input.as_ref()))
+        })
+        .collect::<Vec<_>>();
+    let start = Instant::now();
+    let (records, allocations): (Vec<_>, Vec<_>) = match args.jobs {
+        Some(threads) => {
+            let threads = match threads {
+                0 => thread::available_parallelism().map_or(1, |threads| threads.get()),
+                threads => threads,
+            };
+            runner::run_parallel(&tasks, threads)
+                .into_iter()
+                .map(|record| (record, AllocStats::default()))
+                .unzip()
+        }
+        None => tasks
+            .iter()
+            .map(|(solution, part, input)| alloc::measure(|| runner::run(*solution, *part, input)))
+            .unzip(),
+    };
+    let wall_time = start.elapsed();
+    let total_time = records
+        .iter()
+        .map(|record| record.duration)
+        .sum::<Duration>();
+    match (args.format, args.all) {
+        (Format::Json, _) => records
+            .iter()
+            .zip(&allocations)
+            .for_each(|(record, stats)| {
+                let mut value = record.to_json();
+                if args.alloc_stats {
+                    stats.extend_json(&mut value);
+                }
+                println!("{value}");
+            }),
+        (Format::Text, true) => print!("{}", runner::table(&records)),
+        (Format::Text, false) => {
+            let (solution, _) = jobs[0];
+            println!("Day {}: {}", solution.day(), solution.title());
+            for record in records.iter() {
+                match &record.answer {
+                    Ok(answer) => println!("    {}: {answer}", record.label),
+                    Err(error) => println!("    {}: failed: {error}", record.label),
+                }
+            }
+        }
+    }
+    if args.all || args.jobs.is_some() {
+        print_summary(wall_time, total_time, args.format);
+    }
+    if args.alloc_stats && matches!(args.format, Format::Text) {
+        print!("\nAllocations\n{}", alloc::table(&records, &allocations));
+    }
+    print_metrics(&metrics, args.format);
+    let failures = records
+        .iter()
+        .filter_map(|record| record.answer.as_ref().err())
+        .collect::<Vec<_>>();
+    for error in failures.iter() {
+        eprintln!("\n{}", error.diagnostic());
+    }
+    if let Some(error) = failures.first() {
+        process::exit(error.kind.exit_code());
+    }
+}
+
+/// Wall time of the run next to the sum of the part times, which differ once parts run in parallel
+fn print_summary(wall_time: Duration, total_time: Duration, format: Format) {
+    match format {
+        Format::Text => println!("\nWall time {wall_time:?}, sum of part times {total_time:?}"),
+        Format::Json => println!("{}", json!({ "summary": { "wall_time": wall_time.as_secs_f64(), "total_time": total_time.as_secs_f64() } })),
+    }
+}
+
+fn print_metrics(metrics: &Metrics, format: Format) {
+    if metrics.is_empty() {
+        return;
+    }
+    match format {
+        Format::Text => print!("\nMetrics\n{}", metrics.table()),
+        Format::Json => println!("{}", json!({ "metrics": metrics.to_json() })),
+    }
+}
+
+fn watch(args: &Cli) {
+    let path = args
+        .input
+        .clone()
+        .or_else(|| args.positional_input.clone())
+        .filter(|path| path.as_os_str() != "-");
+    let Some(path) = path else {
+        eprintln!("--watch needs an input file to watch, e.g. --input <path>");
+        process::exit(2);
+    };
+    let (solution, _) = single_day(args);
+    let mut watcher = match Watcher::new(&path) {
+        Ok(watcher) => watcher,
+        Err(error) => {
+            eprintln!("Could not watch {}: {error}", path.display());
+            process::exit(1);
+        }
+    };
+    let mut previous = Vec::new();
+    loop {
+        match Source::File(path.clone()).read(solution) {
+            Ok(input) => {
+                let records = args
+                    .part
+                    .iter()
+                    .map(|part| runner::run(solution, part, &input))
+                    .collect::<Vec<_>>();
+                match args.format {
+                    Format::Text => {
+                        println!("Day {}: {} ({})", solution.day(), solution.title(), Local::now().format("%H:%M:%S"));
+                        print!("{}", watch::compare(&previous, &records));
+                    }
+                    Format::Json => records
+                        .iter()
+                        .for_each(|record| println!("{}", record.to_json())),
+                }
+                previous = records;
+            }
+            Err(error) => eprintln!("Could not read {}: {error}", path.display()),
+        }
+        eprintln!("Watching {} for changes (Ctrl-C to stop)", watcher.path().display());
+        if let Err(error) = watcher.wait(Duration::from_millis(250)) {
+            eprintln!("Could not watch {}: {error}", path.display());
+            process::exit(1);
+        }
+    }
+}
+
+fn single_day(args: &Cli) -> (&'static dyn Solution, Cow<'static, str>) {
+    let solution = match args.day {
+        Some(day) => find(day).unwrap_or_else(|| {
+            eprintln!("Day not found: `{day}`. Implemented days are 1 to {}", latest().day());
+            process::exit(2);
+        }),
+        None => {
+            let (day, note) = calendar::default_day(&Utc::now(), latest().day());
+            if let Some(note) = note {
+                eprintln!("{note}");
+            }
+            find(day).unwrap_or_else(latest)
         }
     };
+    let source = match (
+        args.input
+            .clone()
+            .or_else(|| args.positional_input.clone()),
+        args.example,
+    ) {
+        (Some(path), _) => Source::from(path),
+        (None, Some(number)) => Source::Example(number),
+        // Stdin is only read for an explicit `-`, never because it happens to be a pipe
+        (None, None) => Source::Embedded,
+    };
+    let input = match source.read(solution) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Could not read input {source:?}: {error}");
+            process::exit(ErrorKind::Input.exit_code());
+        }
+    };
+    (solution, input)
+}
+
+fn verify(path: &Path, format: Format) {
+    let answers = match Answers::load(path) {
+        Ok(answers) => answers,
+        Err(error) => {
+            eprintln!("Could not load answers {}: {error}", path.display());
+            process::exit(1);
+        }
+    };
+    let records = SOLUTIONS
+        .iter()
+        .flat_map(|solution| {
+            // The same normalized text a plain run of the day solves
+            let input = match Source::Embedded.read(*solution) {
+                Ok(input) => input,
+                Err(error) => {
+                    eprintln!("Could not read input for day {}: {error}", solution.day());
+                    process::exit(ErrorKind::Input.exit_code());
+                }
+            };
+            Parts::Both
+                .iter()
+                .map(move |part| runner::run(*solution, part, &input))
+        })
+        .collect::<Vec<_>>();
+    let verifications = answers.verify(&records);
+    match format {
+        Format::Text => print!("{}", answers::report(&verifications)),
+        Format::Json => verifications.iter().for_each(|verification| {
+            let mut value = verification.record.to_json();
+            value["expected"] = verification.expected.clone().into();
+            value["status"] = verification.status.to_string().into();
+            println!("{value}");
+        }),
+    }
+    if verifications
+        .iter()
+        .any(|verification| verification.status == answers::Status::Fail)
+    {
+        process::exit(1);
+    }
+}
 
-    match day {
-        1 => day01::run(),
-        2 => day02::run(),
-        3 => day03::run(),
-        4 => day04::run(),
-        5 => day05::run(),
-        6 => day06::run(),
-        7 => day07::run(args.example),
-        8 => day08::run(args.example),
-        9 => day09::run(args.example),
-        _ => {
-            eprintln!("Day not found: `{day}`. Running last available day (day=9)");
-            day09::run(args.example);
+fn new_day(root: &Path, day: usize, name: &str) {
+    match scaffold::create(root, day, name) {
+        Ok(paths) => paths
+            .iter()
+            .for_each(|path| println!("Wrote {}", path.display())),
+        Err(error) => {
+            eprintln!("Could not create day {day}: {error}");
+            process::exit(1);
         }
     }
 }
+
+fn fetch(base_url: &str, day: usize) {
+    let cache = client::default_cache();
+    let result = client::check_day(day).and_then(|()| match cache.get(calendar::YEAR, day) {
+        Some(_) => Ok(cache.path(calendar::YEAR, day)),
+        None => client::session().and_then(|session| client::fetch(&client::Client::new(base_url, &session), &cache, calendar::YEAR, day)),
+    });
+    match result {
+        Ok(path) => println!("{}", path.display()),
+        Err(error) => {
+            eprintln!("Could not fetch day {day}: {error}");
+            process::exit(1);
+        }
+    }
+}
+
+fn submit(base_url: &str, path: &Path, day: usize, part: Part, input: Option<PathBuf>) {
+    let fail = |message: String| -> ! {
+        eprintln!("{message}");
+        process::exit(1);
+    };
+    let solution = find(day).unwrap_or_else(|| fail(format!("Day not found: `{day}`")));
+    let source = input.map(Source::from).unwrap_or_default();
+    let input = source
+        .read(solution)
+        .unwrap_or_else(|error| fail(format!("Could not read input {source:?}: {error}")));
+    let mut answers = Answers::load(path).unwrap_or_else(|error| fail(format!("Could not load answers {}: {error}", path.display())));
+    let record = runner::run(solution, part, &input);
+    let answer = record
+        .answer
+        .unwrap_or_else(|error| fail(format!("Day {day} part {part} failed: {error}")));
+    let session = client::session().unwrap_or_else(|error| fail(error.to_string()));
+    let client = client::Client::new(base_url, &session);
+    let verdict = client::submit(&client, &mut answers, calendar::YEAR, day, part, &answer).unwrap_or_else(|error| fail(format!("Could not submit: {error}")));
+    answers
+        .save(path)
+        .unwrap_or_else(|error| fail(format!("Could not save answers {}: {error}", path.display())));
+    println!("Day {day} part {part}: {answer}");
+    println!("    {verdict}");
+    if verdict != client::Verdict::Correct {
+        process::exit(1);
+    }
+}
+
+fn repl(day: usize, input: Option<PathBuf>, example: Option<usize>) {
+    let fail = |message: String| -> ! {
+        eprintln!("{message}");
+        process::exit(1);
+    };
+    let solution = find(day).unwrap_or_else(|| fail(format!("Day not found: `{day}`")));
+    let source = match (input, example) {
+        // Commands are read from stdin, so it cannot also hold the input
+        (Some(path), _) if path.as_os_str() == "-" => fail("The repl reads commands from stdin; pass the input as a file".to_string()),
+        (Some(path), _) => Source::File(path),
+        (None, Some(number)) => Source::Example(number),
+        (None, None) => Source::Embedded,
+    };
+    let input = source
+        .read(solution)
+        .unwrap_or_else(|error| fail(format!("Could not read input {source:?}: {error}")));
+    if let Err(error) = aoc_2022::repl::run(solution, &input, io::stdin().lock(), io::stdout()) {
+        fail(format!("Repl failed: {error}"));
+    }
+}
+
+fn serve(host: &str, port: u16, workers: usize) {
+    let fail = |message: String| -> ! {
+        eprintln!("{message}");
+        process::exit(1);
+    };
+    let server = aoc_2022::server::Server::bind((host, port))
+        .unwrap_or_else(|error| fail(format!("Could not listen on {host}:{port}: {error}")))
+        .workers(workers);
+    match server.local_addr() {
+        Ok(address) => eprintln!("Listening on http://{address}"),
+        Err(error) => fail(format!("Could not listen on {host}:{port}: {error}")),
+    }
+    server.run();
+}
+
+fn generate(day: usize, size: Option<usize>, seed: Option<u64>) {
+    let Some(generator) = aoc_2022::generate::find(day) else {
+        eprintln!("Day {day} has no input generator");
+        process::exit(1);
+    };
+    let size = size.unwrap_or(generator.default_size);
+    let seed = seed.unwrap_or_else(|| {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+        eprintln!("Seed {seed} ({size} {})", generator.unit);
+        seed
+    });
+    print!("{}", (generator.generate)(&mut aoc_2022::generate::Rng::new(seed), size));
+}
@@ -0,0 +1,287 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde_json::{json, Value};
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+use crate::table;
+
+/// Target that solvers emit metrics under, e.g.
+/// `info!(target: "metrics", rays = 1)` for a counter or
+/// `info!(target: "metrics", parse_time = elapsed.as_secs_f64())` for a timing
+pub const TARGET: &str = "metrics";
+
+/// Integer fields are counters; float fields are timings in seconds
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Kind {
+    Counter,
+    Timing,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Counter => write!(f, "counter"),
+            Self::Timing => write!(f, "timing"),
+        }
+    }
+}
+
+/// A metric name, attributed to the day and part being solved when it was emitted
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Key {
+    pub day: Option<usize>,
+    pub part: Option<usize>,
+    pub name: &'static str,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stat {
+    pub kind: Kind,
+    /// Number of events that reported this metric
+    pub events: u64,
+    pub total: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Stat {
+    fn new(kind: Kind, value: f64) -> Self {
+        Self {
+            kind,
+            events: 1,
+            total: value,
+            min: value,
+            max: value,
+        }
+    }
+
+    fn add(&mut self, value: f64) {
+        self.events += 1;
+        self.total += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.total / self.events as f64
+    }
+
+    fn format(&self, value: f64) -> String {
+        match self.kind {
+            Kind::Counter if value.fract() == 0.0 => format!("{value}"),
+            Kind::Counter => format!("{value:.2}"),
+            Kind::Timing => format!("{:?}", Duration::from_secs_f64(value)),
+        }
+    }
+}
+
+/// Day and part of a `solve` span, used to attribute the events inside it
+#[derive(Clone, Copy, Debug, Default)]
+struct Scope {
+    day: Option<usize>,
+    part: Option<usize>,
+}
+
+impl Visit for Scope {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "day" => self.day = Some(value as usize),
+            "part" => self.part = Some(value as usize),
+            _ => {}
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record_u64(field, value as u64)
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn fmt::Debug) {}
+}
+
+/// The numeric fields of one event
+#[derive(Default)]
+struct Values(Vec<(&'static str, Kind, f64)>);
+
+impl Visit for Values {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0
+            .push((field.name(), Kind::Counter, value as f64))
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0
+            .push((field.name(), Kind::Counter, value as f64))
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.push((field.name(), Kind::Timing, value))
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn fmt::Debug) {}
+}
+
+/// A layer aggregating `metrics` events; clones share the same totals
+#[derive(Clone, Debug, Default)]
+pub struct Metrics {
+    stats: Arc<Mutex<BTreeMap<Key, Stat>>>,
+}
+
+impl Metrics {
+    pub fn snapshot(&self) -> Vec<(Key, Stat)> {
+        self.stats
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(key, stat)| (*key, *stat))
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stats.lock().unwrap().is_empty()
+    }
+
+    /// One object per metric, with `total`, `min`, `max` and `mean` in seconds for timings
+    pub fn to_json(&self) -> Value {
+        self.snapshot()
+            .iter()
+            .map(|(key, stat)| {
+                json!({
+                    "day": key.day,
+                    "part": key.part,
+                    "metric": key.name,
+                    "kind": stat.kind.to_string(),
+                    "events": stat.events,
+                    "total": stat.total,
+                    "min": stat.min,
+                    "max": stat.max,
+                    "mean": stat.mean(),
+                })
+            })
+            .collect()
+    }
+
+    /// Renders the summary as an aligned table
+    pub fn table(&self) -> String {
+        let dash = |value: Option<usize>| value.map_or_else(|| "-".to_string(), |value| value.to_string());
+        let rows = self
+            .snapshot()
+            .iter()
+            .map(|(key, stat)| {
+                [
+                    dash(key.day),
+                    dash(key.part),
+                    key.name.to_string(),
+                    stat.events.to_string(),
+                    stat.format(stat.total),
+                    stat.format(stat.mean()),
+                    stat.format(stat.max),
+                ]
+            })
+            .collect::<Vec<_>>();
+        table::render(["Day", "Part", "Metric", "Events", "Total", "Mean", "Max"], &rows)
+    }
+}
+
+impl<S> Layer<S> for Metrics
+where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let mut scope = Scope::default();
+        attrs.record(&mut scope);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(scope);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut values = Values::default();
+        event.record(&mut values);
+        if values.0.is_empty() {
+            return;
+        }
+        let scope = ctx
+            .event_scope(event)
+            .and_then(|mut spans| spans.find_map(|span| span.extensions().get::<Scope>().copied()))
+            .unwrap_or_default();
+        let mut stats = self.stats.lock().unwrap();
+        for (name, kind, value) in values.0 {
+            let key = Key {
+                day: scope.day,
+                part: scope.part,
+                name,
+            };
+            stats
+                .entry(key)
+                .and_modify(|stat| stat.add(value))
+                .or_insert_with(|| Stat::new(kind, value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find, runner, Part};
+
+    use tracing::{info, info_span};
+    use tracing_subscriber::prelude::*;
+
+    fn collect(f: impl FnOnce()) -> Metrics {
+        let metrics = Metrics::default();
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(metrics.clone()), f);
+        metrics
+    }
+
+    #[test]
+    pub fn test_aggregates_by_scope() {
+        let metrics = collect(|| {
+            info!(target: "metrics", rays = 1, ray_cells = 4);
+            let _span = info_span!(target: "metrics", "solve", day = 8, part = 2).entered();
+            info!(target: "metrics", rays = 1, ray_cells = 2);
+            info!(target: "metrics", rays = 1, ray_cells = 6);
+            info!(target: "metrics", parse_time = 0.25);
+            info!(target: "metrics", "text only");
+        });
+        let stats = metrics.snapshot();
+        let get = |day, part, name| {
+            stats
+                .iter()
+                .find(|(key, _)| *key == Key { day, part, name })
+                .map(|(_, stat)| (stat.kind, stat.events, stat.total, stat.max))
+        };
+        assert_eq!(stats.len(), 5);
+        assert_eq!(get(None, None, "ray_cells"), Some((Kind::Counter, 1, 4.0, 4.0)));
+        assert_eq!(get(Some(8), Some(2), "rays"), Some((Kind::Counter, 2, 2.0, 1.0)));
+        assert_eq!(get(Some(8), Some(2), "ray_cells"), Some((Kind::Counter, 2, 8.0, 6.0)));
+        assert_eq!(get(Some(8), Some(2), "parse_time"), Some((Kind::Timing, 1, 0.25, 0.25)));
+    }
+
+    #[test]
+    pub fn test_solver_metrics() {
+        let solution = find(9).unwrap();
+        let metrics = collect(|| {
+            runner::run(solution, Part::Two, solution.example(1).unwrap());
+        });
+        let json = metrics.to_json();
+        let knot_updates = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|metric| metric["metric"] == "knot_updates")
+            .unwrap();
+        assert_eq!(knot_updates["day"], 9);
+        assert_eq!(knot_updates["part"], 2);
+        assert_eq!(knot_updates["kind"], "counter");
+        assert!(metrics.table().contains("knot_updates"));
+    }
+}
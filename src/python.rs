@@ -0,0 +1,379 @@
+//! Python bindings, built with `cargo build --features python`.
+//!
+//! Each day's solver module becomes a submodule of `aoc_2022` with the same
+//! name, e.g. `aoc_2022.calories.calculate(text, 3)`.  Solver errors are
+//! raised as `aoc_2022.AocError`, a `ValueError` carrying `kind`, `line` and
+//! `column` attributes.
+
+use pyo3::{create_exception, exceptions::PyValueError, prelude::*};
+
+use crate::{find, Part, SOLUTIONS};
+
+create_exception!(aoc_2022, AocError, PyValueError, "A solver rejected its input");
+
+impl From<crate::AocError> for PyErr {
+    fn from(error: crate::AocError) -> Self {
+        let exception = AocError::new_err(error.to_string());
+        Python::with_gil(|py| {
+            let value = exception.value(py);
+            let attributes = [value.setattr("kind", error.kind.to_string()), value.setattr("line", error.line), value.setattr("column", error.column)];
+            match attributes.into_iter().find_map(Result::err) {
+                Some(error) => error,
+                None => exception,
+            }
+        })
+    }
+}
+
+/// Solves `part` of `day` against `text`, or the embedded input when `text` is `None`
+#[pyfunction]
+#[pyo3(signature = (day, part, text = None))]
+fn solve(day: usize, part: usize, text: Option<&str>) -> PyResult<String> {
+    let solution = find(day).ok_or_else(|| PyValueError::new_err(format!("Day not found: `{day}`")))?;
+    let part = part
+        .to_string()
+        .parse::<Part>()
+        .map_err(PyValueError::new_err)?;
+    Ok(solution.solve(part, text.unwrap_or(solution.input()))?)
+}
+
+/// `(day, title)` of every implemented day
+#[pyfunction]
+fn days() -> Vec<(usize, &'static str)> {
+    SOLUTIONS
+        .iter()
+        .map(|solution| (solution.day(), solution.title()))
+        .collect()
+}
+
+mod calories {
+    use super::*;
+    use crate::day01::calories;
+
+    /// Calorie counts grouped by elf
+    #[pyfunction]
+    fn parse(text: &str) -> PyResult<Vec<Vec<usize>>> {
+        Ok(calories::parse(text)?)
+    }
+
+    /// Calories carried by the `count` elves carrying the most
+    #[pyfunction]
+    fn calculate(text: &str, count: usize) -> PyResult<usize> {
+        Ok(calories::calculate(Some(text), count)?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(parse, m)?)?;
+        m.add_function(wrap_pyfunction!(calculate, m)?)
+    }
+}
+
+mod rock_paper {
+    use super::*;
+    use crate::day02::rock_paper;
+
+    #[pyfunction]
+    fn play_round_1(text: &str) -> PyResult<usize> {
+        Ok(rock_paper::play_round_1(text)?)
+    }
+
+    #[pyfunction]
+    fn play_round_2(text: &str) -> PyResult<usize> {
+        Ok(rock_paper::play_round_2(text)?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(play_round_1, m)?)?;
+        m.add_function(wrap_pyfunction!(play_round_2, m)?)
+    }
+}
+
+mod rucksack {
+    use super::*;
+    use crate::day03::rucksack;
+
+    #[pyfunction]
+    fn prioritize(text: &str) -> PyResult<usize> {
+        Ok(rucksack::prioritize(text)?)
+    }
+
+    #[pyfunction]
+    fn find_badge_priority(text: &str) -> PyResult<usize> {
+        Ok(rucksack::find_badge_priority(text)?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(prioritize, m)?)?;
+        m.add_function(wrap_pyfunction!(find_badge_priority, m)?)
+    }
+}
+
+mod camp_cleaning {
+    use super::*;
+    use crate::day04::camp_cleaning;
+
+    type Range = (usize, usize);
+
+    /// Both elves' `(min, max)` section ranges, one pair per line
+    #[pyfunction]
+    fn parse_pairs(text: &str) -> PyResult<Vec<(Range, Range)>> {
+        let mut pairs = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            if camp_cleaning::ignore_empty(&line) {
+                let [left, right] = camp_cleaning::parse_pair(index, line)?;
+                pairs.push((left, right));
+            }
+        }
+        Ok(pairs)
+    }
+
+    #[pyfunction]
+    fn count_fully_contained(text: &str) -> PyResult<usize> {
+        Ok(camp_cleaning::count_fully_contained(text)?)
+    }
+
+    #[pyfunction]
+    fn count_partial_overlap(text: &str) -> PyResult<usize> {
+        Ok(camp_cleaning::count_partial_overlap(text)?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(parse_pairs, m)?)?;
+        m.add_function(wrap_pyfunction!(count_fully_contained, m)?)?;
+        m.add_function(wrap_pyfunction!(count_partial_overlap, m)?)
+    }
+}
+
+mod supply_stacks {
+    use super::*;
+    use crate::day05::supply_stacks;
+
+    /// One rearrangement step, with 1-based stack numbers
+    #[pyclass(frozen, get_all, module = "aoc_2022.supply_stacks")]
+    struct Move {
+        count: usize,
+        origination: usize,
+        destination: usize,
+    }
+
+    #[pymethods]
+    impl Move {
+        fn __repr__(&self) -> String {
+            format!("Move(count={}, origination={}, destination={})", self.count, self.origination, self.destination)
+        }
+    }
+
+    /// Each stack's crates, bottom crate first
+    #[pyfunction]
+    fn parse_stacks(text: &str) -> PyResult<Vec<Vec<String>>> {
+        Ok(supply_stacks::parse_stacks(text)?)
+    }
+
+    #[pyfunction]
+    fn parse_moves(text: &str) -> PyResult<Vec<Move>> {
+        Ok(supply_stacks::parse_moves(text)?
+            .into_iter()
+            .map(|movement| Move {
+                count: movement.count,
+                origination: movement.origination,
+                destination: movement.destination,
+            })
+            .collect())
+    }
+
+    /// The top crate of each stack after moving crates one at a time
+    #[pyfunction]
+    fn arrange_crates_9000(text: &str) -> PyResult<Vec<String>> {
+        Ok(supply_stacks::arrange_crates_9000(text)?)
+    }
+
+    /// The top crate of each stack after moving crates several at a time
+    #[pyfunction]
+    fn arrange_crates_9001(text: &str) -> PyResult<Vec<String>> {
+        Ok(supply_stacks::arrange_crates_9001(text)?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_class::<Move>()?;
+        m.add_function(wrap_pyfunction!(parse_stacks, m)?)?;
+        m.add_function(wrap_pyfunction!(parse_moves, m)?)?;
+        m.add_function(wrap_pyfunction!(arrange_crates_9000, m)?)?;
+        m.add_function(wrap_pyfunction!(arrange_crates_9001, m)?)
+    }
+}
+
+mod tuning_trouble {
+    use super::*;
+    use crate::day06::tuning_trouble;
+
+    /// Characters read once the last `size` characters are all different
+    #[pyfunction]
+    fn detect_start(text: &str, size: usize) -> PyResult<usize> {
+        Ok(tuning_trouble::detect_start(text, size)?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(detect_start, m)?)
+    }
+}
+
+mod no_space {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::day07::no_space::{self, File as Entry};
+
+    /// A file or directory from the terminal log; a directory's size includes everything below it
+    #[pyclass(frozen, get_all, module = "aoc_2022.no_space")]
+    struct File {
+        path: String,
+        is_dir: bool,
+        size: usize,
+    }
+
+    #[pymethods]
+    impl File {
+        fn __repr__(&self) -> String {
+            format!("File(path={:?}, is_dir={}, size={})", self.path, if self.is_dir { "True" } else { "False" }, self.size)
+        }
+    }
+
+    /// Every path in the log, mapped to its `File`
+    #[pyfunction]
+    fn build_sizes(text: &str) -> PyResult<BTreeMap<String, File>> {
+        Ok(no_space::build_sizes(text)?
+            .into_iter()
+            .map(|(path, entry)| {
+                let file = File {
+                    path: path.clone(),
+                    is_dir: matches!(entry, Entry::Directory(_)),
+                    size: entry.size(),
+                };
+                (path, file)
+            })
+            .collect())
+    }
+
+    #[pyfunction]
+    fn find_good_deletion_candidates(text: &str) -> PyResult<usize> {
+        Ok(no_space::find_good_deletion_candidates(text)?)
+    }
+
+    #[pyfunction]
+    fn folder_to_delete(text: &str) -> PyResult<usize> {
+        Ok(no_space::folder_to_delete(text)?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_class::<File>()?;
+        m.add_function(wrap_pyfunction!(build_sizes, m)?)?;
+        m.add_function(wrap_pyfunction!(find_good_deletion_candidates, m)?)?;
+        m.add_function(wrap_pyfunction!(folder_to_delete, m)?)
+    }
+}
+
+mod tree_top {
+    use super::*;
+    use crate::day08::tree_top;
+
+    /// Tree heights, one list per row
+    #[pyfunction]
+    fn parse_tree(text: &str) -> PyResult<Vec<Vec<usize>>> {
+        let mut rows: Vec<Vec<usize>> = Vec::new();
+        // Positions sort by row, then column
+        for (position, cell) in tree_top::parse_tree(text)? {
+            match rows.get_mut(position.y as usize) {
+                Some(row) => row.push(cell.value()),
+                None => rows.push(vec![cell.value()]),
+            }
+        }
+        Ok(rows)
+    }
+
+    #[pyfunction]
+    fn find_trees_visible(text: &str) -> PyResult<usize> {
+        Ok(tree_top::find_trees_visible(text)?)
+    }
+
+    #[pyfunction]
+    fn find_highest_scenic_score(text: &str) -> PyResult<usize> {
+        Ok(tree_top::find_highest_scenic_score(text)?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(parse_tree, m)?)?;
+        m.add_function(wrap_pyfunction!(find_trees_visible, m)?)?;
+        m.add_function(wrap_pyfunction!(find_highest_scenic_score, m)?)
+    }
+}
+
+mod rope_bridge {
+    use super::*;
+    use crate::day09::rope_bridge;
+
+    /// One step of the head, e.g. `Update(direction="Right", distance=1)`
+    #[pyclass(frozen, get_all, module = "aoc_2022.rope_bridge")]
+    struct Update {
+        direction: String,
+        distance: usize,
+    }
+
+    #[pymethods]
+    impl Update {
+        fn __repr__(&self) -> String {
+            format!("Update(direction={:?}, distance={})", self.direction, self.distance)
+        }
+    }
+
+    /// The head's moves, split into single steps
+    #[pyfunction]
+    fn parse_moves(text: &str) -> PyResult<Vec<Update>> {
+        Ok(rope_bridge::parse_moves(text)?
+            .into_iter()
+            .map(|update| Update {
+                direction: update.direction.to_string(),
+                distance: update.distance,
+            })
+            .collect())
+    }
+
+    /// Positions visited by the last of `knots` knots
+    #[pyfunction]
+    fn count_tail_position(text: &str, knots: usize) -> PyResult<usize> {
+        Ok(rope_bridge::count_tail_position(text, knots)?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_class::<Update>()?;
+        m.add_function(wrap_pyfunction!(parse_moves, m)?)?;
+        m.add_function(wrap_pyfunction!(count_tail_position, m)?)
+    }
+}
+
+/// Adds `name` as a submodule, also registered in `sys.modules` so that
+/// `import aoc_2022.<name>` works alongside `from aoc_2022 import <name>`
+fn add_submodule(parent: &Bound<'_, PyModule>, name: &str, register: fn(&Bound<'_, PyModule>) -> PyResult<()>) -> PyResult<()> {
+    let py = parent.py();
+    let module = PyModule::new(py, name)?;
+    register(&module)?;
+    parent.add_submodule(&module)?;
+    py.import("sys")?
+        .getattr("modules")?
+        .set_item(format!("aoc_2022.{name}"), &module)
+}
+
+#[pymodule]
+fn aoc_2022(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("AocError", m.py().get_type::<AocError>())?;
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_function(wrap_pyfunction!(days, m)?)?;
+    add_submodule(m, "calories", calories::register)?;
+    add_submodule(m, "rock_paper", rock_paper::register)?;
+    add_submodule(m, "rucksack", rucksack::register)?;
+    add_submodule(m, "camp_cleaning", camp_cleaning::register)?;
+    add_submodule(m, "supply_stacks", supply_stacks::register)?;
+    add_submodule(m, "tuning_trouble", tuning_trouble::register)?;
+    add_submodule(m, "no_space", no_space::register)?;
+    add_submodule(m, "tree_top", tree_top::register)?;
+    add_submodule(m, "rope_bridge", rope_bridge::register)
+}
@@ -0,0 +1,483 @@
+//! Deliberately simple reference solvers, checked against the real ones by
+//! property tests.  These work on already-structured puzzle data and favour
+//! obvious loops over speed, so any disagreement points at the optimized
+//! solver (or its parser) rather than at the reference.
+
+use std::collections::HashSet;
+
+const ITEMS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Day 1: the `count` largest elf totals added together
+fn calories(elves: &[Vec<usize>], count: usize) -> usize {
+    let mut totals = elves
+        .iter()
+        .map(|elf| elf.iter().sum::<usize>())
+        .collect::<Vec<_>>();
+    totals.sort();
+    totals.iter().rev().take(count).sum()
+}
+
+/// Day 2: rounds are `(opponent, column)` with 0, 1, 2 for rock, paper,
+/// scissors or for lose, draw, win
+fn rock_paper(rounds: &[(usize, usize)], column_is_outcome: bool) -> usize {
+    let mut total = 0;
+    for &(opponent, column) in rounds {
+        for me in 0..3 {
+            // Each shape beats the one before it
+            let outcome = if me == opponent {
+                1
+            } else if me == (opponent + 1) % 3 {
+                2
+            } else {
+                0
+            };
+            let chosen = match column_is_outcome {
+                true => outcome == column,
+                false => me == column,
+            };
+            if chosen {
+                total += me + 1 + outcome * 3;
+            }
+        }
+    }
+    total
+}
+
+fn priority(item: char) -> usize {
+    ITEMS.find(item).unwrap() + 1
+}
+
+/// Day 3, part 1: the item found in both halves of each rucksack
+fn rucksacks(rucksacks: &[String]) -> usize {
+    let mut total = 0;
+    for rucksack in rucksacks {
+        let (left, right) = rucksack.split_at(rucksack.len() / 2);
+        let shared = ITEMS
+            .chars()
+            .filter(|item| left.contains(*item) && right.contains(*item))
+            .collect::<Vec<_>>();
+        assert_eq!(shared.len(), 1, "{rucksack} must share exactly one item");
+        total += priority(shared[0]);
+    }
+    total
+}
+
+/// Day 3, part 2: the item carried by all three elves of each group
+fn badges(rucksacks: &[String]) -> usize {
+    let mut total = 0;
+    for group in rucksacks.chunks(3) {
+        let shared = ITEMS
+            .chars()
+            .filter(|item| {
+                group
+                    .iter()
+                    .all(|rucksack| rucksack.contains(*item))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(shared.len(), 1, "{group:?} must share exactly one badge");
+        total += priority(shared[0]);
+    }
+    total
+}
+
+/// Day 4: pairs of sections, counted section by section
+fn camp_cleaning(pairs: &[[(usize, usize); 2]], fully: bool) -> usize {
+    let mut count = 0;
+    for [(a_start, a_end), (b_start, b_end)] in pairs {
+        let a = (*a_start..=*a_end).collect::<HashSet<_>>();
+        let b = (*b_start..=*b_end).collect::<HashSet<_>>();
+        let matches = match fully {
+            true => a.is_subset(&b) || b.is_subset(&a),
+            false => !a.is_disjoint(&b),
+        };
+        count += usize::from(matches);
+    }
+    count
+}
+
+/// Day 5: moves are `(count, from, to)` with 0-based stacks; `None` when a
+/// stack ends up empty
+fn supply_stacks(stacks: &[Vec<char>], moves: &[(usize, usize, usize)], one_at_a_time: bool) -> Option<String> {
+    let mut stacks = stacks.to_vec();
+    for &(count, from, to) in moves {
+        let mut lifted = vec![];
+        for _ in 0..count {
+            lifted.push(stacks[from].pop().unwrap());
+        }
+        if !one_at_a_time {
+            lifted.reverse();
+        }
+        stacks[to].extend(lifted);
+    }
+    stacks.iter().map(|stack| stack.last()).collect()
+}
+
+/// Day 6: characters read up to the end of the first `size` distinct ones
+fn tuning_trouble(stream: &str, size: usize) -> Option<usize> {
+    let chars = stream.chars().collect::<Vec<_>>();
+    (size..=chars.len()).find(|&end| {
+        let window = &chars[end - size..end];
+        (0..size).all(|i| (0..size).all(|j| i == j || window[i] != window[j]))
+    })
+}
+
+/// Day 7: a directory holding file sizes and subdirectories
+#[derive(Clone, Debug)]
+struct Directory {
+    files: Vec<usize>,
+    children: Vec<Directory>,
+}
+
+impl Directory {
+    fn size(&self) -> usize {
+        self.files.iter().sum::<usize>()
+            + self
+                .children
+                .iter()
+                .map(Directory::size)
+                .sum::<usize>()
+    }
+
+    /// Sizes of this directory and every one below it
+    fn sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![self.size()];
+        for child in &self.children {
+            sizes.extend(child.sizes());
+        }
+        sizes
+    }
+}
+
+fn no_space_small(root: &Directory) -> usize {
+    root.sizes()
+        .into_iter()
+        .filter(|size| *size <= 100_000)
+        .sum()
+}
+
+fn no_space_delete(root: &Directory) -> usize {
+    let needed = root
+        .size()
+        .saturating_sub(70_000_000 - 30_000_000);
+    root.sizes()
+        .into_iter()
+        .filter(|size| *size >= needed)
+        .min()
+        .unwrap()
+}
+
+/// Day 8: trees seen from outside the grid
+fn trees_visible(grid: &[Vec<u8>]) -> usize {
+    let (height, width) = (grid.len(), grid[0].len());
+    let mut count = 0;
+    for y in 0..height {
+        for x in 0..width {
+            let tree = grid[y][x];
+            let left = (0..x).all(|i| grid[y][i] < tree);
+            let right = (x + 1..width).all(|i| grid[y][i] < tree);
+            let up = (0..y).all(|i| grid[i][x] < tree);
+            let down = (y + 1..height).all(|i| grid[i][x] < tree);
+            count += usize::from(left || right || up || down);
+        }
+    }
+    count
+}
+
+/// Day 8: the best product of viewing distances
+fn scenic_score(grid: &[Vec<u8>]) -> usize {
+    let (height, width) = (grid.len() as i32, grid[0].len() as i32);
+    let mut best = 0;
+    for y in 0..height {
+        for x in 0..width {
+            let tree = grid[y as usize][x as usize];
+            let mut score = 1;
+            for (dy, dx) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                let (mut ny, mut nx, mut distance) = (y + dy, x + dx, 0);
+                while (0..height).contains(&ny) && (0..width).contains(&nx) {
+                    distance += 1;
+                    if grid[ny as usize][nx as usize] >= tree {
+                        break;
+                    }
+                    (ny, nx) = (ny + dy, nx + dx);
+                }
+                score *= distance;
+            }
+            best = best.max(score);
+        }
+    }
+    best
+}
+
+/// Day 9: moves are a direction letter and a distance, simulated one step at
+/// a time
+fn rope_bridge(moves: &[(char, usize)], knots: usize) -> usize {
+    let mut rope = vec![(0i32, 0i32); knots];
+    let mut visited = HashSet::from([(0, 0)]);
+    for &(direction, distance) in moves {
+        let (dy, dx) = match direction {
+            'U' => (1, 0),
+            'D' => (-1, 0),
+            'L' => (0, -1),
+            _ => (0, 1),
+        };
+        for _ in 0..distance {
+            rope[0] = (rope[0].0 + dy, rope[0].1 + dx);
+            for knot in 1..knots {
+                let (gap_y, gap_x) = (rope[knot - 1].0 - rope[knot].0, rope[knot - 1].1 - rope[knot].1);
+                if gap_y.abs() > 1 || gap_x.abs() > 1 {
+                    rope[knot] = (rope[knot].0 + gap_y.signum(), rope[knot].1 + gap_x.signum());
+                }
+            }
+            visited.insert(rope[knots - 1]);
+        }
+    }
+    visited.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::borrow::Cow;
+
+    use proptest::{collection::vec, prelude::*, sample::Index};
+
+    use crate::{
+        day01::calories, day02::rock_paper, day03::rucksack, day04::camp_cleaning, day05::supply_stacks, day06::tuning_trouble, day07::no_space, day08::tree_top, day09::rope_bridge, input::normalize,
+    };
+
+    /// Starting stacks and `(count, from, to)` moves
+    type Crates = (Vec<Vec<char>>, Vec<(usize, usize, usize)>);
+
+    /// Puzzle text as a real run hands it to the solver, ending in a newline
+    fn input(text: &str) -> String {
+        normalize(Cow::Borrowed(text)).into_owned()
+    }
+
+    fn item() -> impl Strategy<Value = char> {
+        (0..52usize).prop_map(|index| ITEMS.as_bytes()[index] as char)
+    }
+
+    /// A rucksack whose halves share exactly one item type
+    fn packed_rucksack() -> impl Strategy<Value = String> {
+        (item(), vec(item(), 0..8), vec(item(), 0..8)).prop_map(|(shared, left, right)| {
+            let mut left = left
+                .into_iter()
+                .filter(|item| *item != shared)
+                .collect::<Vec<_>>();
+            let mut right = right
+                .into_iter()
+                .filter(|item| *item != shared && !left.contains(item))
+                .collect::<Vec<_>>();
+            left.push(shared);
+            right.push(shared);
+            let length = left.len().max(right.len());
+            left.resize(length, shared);
+            right.resize(length, shared);
+            left.into_iter().chain(right).collect()
+        })
+    }
+
+    /// Three rucksacks with exactly one item type in common
+    fn group() -> impl Strategy<Value = Vec<String>> {
+        (item(), vec(item(), 0..8), vec(item(), 0..8), vec(item(), 0..8)).prop_map(|(badge, first, second, third)| {
+            let third = third
+                .into_iter()
+                .filter(|item| !(first.contains(item) && second.contains(item)))
+                .collect::<Vec<_>>();
+            [first, second, third]
+                .into_iter()
+                .map(|items| {
+                    items
+                        .into_iter()
+                        .filter(|item| *item != badge)
+                        .chain([badge])
+                        .collect()
+                })
+                .collect()
+        })
+    }
+
+    fn section() -> impl Strategy<Value = (usize, usize)> {
+        (1..100usize, 0..30usize).prop_map(|(start, length)| (start, start + length))
+    }
+
+    /// Stacks and moves that only ever lift crates that are there
+    fn crates() -> impl Strategy<Value = Crates> {
+        let stacks = vec(vec((b'A'..=b'Z').prop_map(char::from), 1..6), 2..10);
+        (stacks, vec(any::<(Index, Index, Index)>(), 0..20)).prop_map(|(stacks, choices)| {
+            let mut heights = stacks.iter().map(Vec::len).collect::<Vec<_>>();
+            let mut moves = vec![];
+            for (from, to, count) in choices {
+                let sources = (0..heights.len())
+                    .filter(|stack| heights[*stack] > 0)
+                    .collect::<Vec<_>>();
+                let from = sources[from.index(sources.len())];
+                let to = match to.index(heights.len() - 1) {
+                    to if to >= from => to + 1,
+                    to => to,
+                };
+                let count = 1 + count.index(heights[from]);
+                heights[from] -= count;
+                heights[to] += count;
+                moves.push((count, from, to));
+            }
+            (stacks, moves)
+        })
+    }
+
+    fn render_crates(stacks: &[Vec<char>], moves: &[(usize, usize, usize)]) -> String {
+        let height = stacks.iter().map(Vec::len).max().unwrap();
+        let mut text = String::new();
+        for level in (0..height).rev() {
+            let row = stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(item) => format!("[{item}]"),
+                    None => "   ".to_string(),
+                })
+                .collect::<Vec<_>>();
+            text.push_str(&format!("{}\n", row.join(" ")));
+        }
+        let numbers = (1..=stacks.len())
+            .map(|number| format!(" {number} "))
+            .collect::<Vec<_>>();
+        text.push_str(&format!("{}\n\n", numbers.join(" ")));
+        for (count, from, to) in moves {
+            text.push_str(&format!("move {count} from {} to {}\n", from + 1, to + 1));
+        }
+        text
+    }
+
+    /// A packet size and a stream that may or may not hold a marker of it
+    fn datastream() -> impl Strategy<Value = (usize, String)> {
+        let letters = ('a'..='p').collect::<Vec<_>>();
+        let marker = prop::option::of(Just(letters).prop_shuffle());
+        (1..=14usize, "[a-p]{0,30}", marker, "[a-p]{0,30}").prop_map(|(size, before, marker, after)| {
+            let marker = marker
+                .unwrap_or_default()
+                .into_iter()
+                .take(size)
+                .collect::<String>();
+            (size, format!("{before}{marker}{after}"))
+        })
+    }
+
+    fn file_size() -> impl Strategy<Value = usize> {
+        // Large files push the disk past the point where part 2 deletes anything
+        prop_oneof![1..=100_000usize, 1..=20_000_000usize]
+    }
+
+    /// A root directory holding at least one file
+    fn file_system() -> impl Strategy<Value = Directory> {
+        let leaf = vec(file_size(), 0..4).prop_map(|files| Directory { files, children: vec![] });
+        let directory = leaf.prop_recursive(3, 20, 3, |inner| (vec(file_size(), 0..4), vec(inner, 0..3)).prop_map(|(files, children)| Directory { files, children }));
+        (vec(file_size(), 1..4), vec(directory, 0..3)).prop_map(|(files, children)| Directory { files, children })
+    }
+
+    fn render_directory(directory: &Directory, text: &mut String) {
+        text.push_str("$ ls\n");
+        for index in 0..directory.children.len() {
+            text.push_str(&format!("dir d{index}\n"));
+        }
+        for (index, size) in directory.files.iter().enumerate() {
+            text.push_str(&format!("{size} f{index}.txt\n"));
+        }
+        for (index, child) in directory.children.iter().enumerate() {
+            text.push_str(&format!("$ cd d{index}\n"));
+            render_directory(child, text);
+            text.push_str("$ cd ..\n");
+        }
+    }
+
+    fn grid() -> impl Strategy<Value = Vec<Vec<u8>>> {
+        (3..=8usize, 3..=8usize).prop_flat_map(|(height, width)| vec(vec(0..=9u8, width), height))
+    }
+
+    fn rope_moves() -> impl Strategy<Value = Vec<(char, usize)>> {
+        vec((prop::sample::select(vec!['U', 'D', 'L', 'R']), 1..=12usize), 0..30)
+    }
+
+    proptest! {
+        #[test]
+        fn test_calories(elves in vec(vec(0..100_000usize, 1..8), 1..10), count in 1..=3usize) {
+            let text = elves
+                .iter()
+                .map(|elf| elf.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            prop_assert_eq!(calories::calculate(Some(&input(&text)), count).unwrap(), super::calories(&elves, count));
+        }
+
+        #[test]
+        fn test_rock_paper(rounds in vec((0..3usize, 0..3usize), 0..40)) {
+            let text = rounds
+                .iter()
+                .map(|(opponent, column)| format!("{} {}\n", ["A", "B", "C"][*opponent], ["X", "Y", "Z"][*column]))
+                .collect::<String>();
+            prop_assert_eq!(rock_paper::play_round_1(&input(&text)).unwrap(), super::rock_paper(&rounds, false));
+            prop_assert_eq!(rock_paper::play_round_2(&input(&text)).unwrap(), super::rock_paper(&rounds, true));
+        }
+
+        #[test]
+        fn test_rucksack(rucksacks in vec(packed_rucksack(), 0..12)) {
+            prop_assert_eq!(rucksack::prioritize(&input(&rucksacks.join("\n"))).unwrap(), super::rucksacks(&rucksacks));
+        }
+
+        #[test]
+        fn test_badges(groups in vec(group(), 0..5)) {
+            let rucksacks = groups.concat();
+            prop_assert_eq!(rucksack::find_badge_priority(&input(&rucksacks.join("\n"))).unwrap(), badges(&rucksacks));
+        }
+
+        #[test]
+        fn test_camp_cleaning(pairs in vec([section(), section()], 0..30)) {
+            let text = pairs
+                .iter()
+                .map(|[(a, b), (c, d)]| format!("{a}-{b},{c}-{d}\n"))
+                .collect::<String>();
+            prop_assert_eq!(camp_cleaning::count_fully_contained(&input(&text)).unwrap(), super::camp_cleaning(&pairs, true));
+            prop_assert_eq!(camp_cleaning::count_partial_overlap(&input(&text)).unwrap(), super::camp_cleaning(&pairs, false));
+        }
+
+        #[test]
+        fn test_supply_stacks((stacks, moves) in crates()) {
+            let text = render_crates(&stacks, &moves);
+            let top = |crates: Result<Vec<String>, _>| crates.ok().map(|crates| crates.concat());
+            prop_assert_eq!(top(supply_stacks::arrange_crates_9000(&input(&text))), super::supply_stacks(&stacks, &moves, true));
+            prop_assert_eq!(top(supply_stacks::arrange_crates_9001(&input(&text))), super::supply_stacks(&stacks, &moves, false));
+        }
+
+        #[test]
+        fn test_detect_start((size, stream) in datastream()) {
+            prop_assert_eq!(tuning_trouble::detect_start(&input(&stream), size).ok(), super::tuning_trouble(&stream, size));
+        }
+
+        #[test]
+        fn test_no_space(root in file_system()) {
+            let mut text = String::from("$ cd /\n");
+            render_directory(&root, &mut text);
+            prop_assert_eq!(no_space::find_good_deletion_candidates(&input(&text)).unwrap(), no_space_small(&root));
+            prop_assert_eq!(no_space::folder_to_delete(&input(&text)).unwrap(), no_space_delete(&root));
+        }
+
+        #[test]
+        fn test_tree_top(grid in grid()) {
+            let text = grid
+                .iter()
+                .map(|row| row.iter().map(|tree| tree.to_string()).collect::<String>() + "\n")
+                .collect::<String>();
+            prop_assert_eq!(tree_top::find_trees_visible(&input(&text)).unwrap(), trees_visible(&grid));
+            prop_assert_eq!(tree_top::find_highest_scenic_score(&input(&text)).unwrap(), scenic_score(&grid));
+        }
+
+        #[test]
+        fn test_count_tail_position(moves in rope_moves(), knots in 1..=10usize) {
+            let text = moves
+                .iter()
+                .map(|(direction, distance)| format!("{direction} {distance}\n"))
+                .collect::<String>();
+            prop_assert_eq!(rope_bridge::count_tail_position(&input(&text), knots).unwrap(), super::rope_bridge(&moves, knots));
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::{
+    io::{self, BufRead, Write},
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::{
+    runner::{self, panic_message},
+    Explore, Part, Solution,
+};
+
+const COMMANDS: &[(&str, &str)] = &[
+    ("part1", "Solve part 1 against the loaded input"),
+    ("part2", "Solve part 2 against the loaded input"),
+    ("help", "List the available commands"),
+    ("quit", "Leave the repl (or press Ctrl-D)"),
+];
+
+enum Reply {
+    Output(String),
+    Error(String),
+    Quit,
+}
+
+fn help(explorer: Option<&dyn Explore>) -> String {
+    let day_commands = explorer.map_or(&[][..], |explorer| explorer.commands());
+    let width = COMMANDS
+        .iter()
+        .chain(day_commands)
+        .map(|(usage, _)| usage.len())
+        .max()
+        .unwrap_or_default();
+    COMMANDS
+        .iter()
+        .chain(day_commands)
+        .map(|(usage, description)| format!("  {usage:width$}  {description}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn execute(solution: &dyn Solution, explorer: &mut Option<Box<dyn Explore>>, input: &str, line: &str) -> Reply {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+    let (command, args) = match words.split_first() {
+        Some((command, args)) => (*command, args),
+        None => return Reply::Output(String::new()),
+    };
+    // Day commands come first so a day can give `set`/`show` whatever meaning it needs
+    if let Some(explorer) = explorer {
+        match panic::catch_unwind(AssertUnwindSafe(|| explorer.run(command, args))) {
+            Ok(Some(Ok(output))) => return Reply::Output(output),
+            Ok(Some(Err(error))) => return Reply::Error(error),
+            Ok(None) => {}
+            Err(payload) => return Reply::Error(panic_message(payload)),
+        }
+    }
+    let part = match command {
+        "part1" => Part::One,
+        "part2" => Part::Two,
+        "help" | "?" => return Reply::Output(help(explorer.as_deref())),
+        "quit" | "exit" => return Reply::Quit,
+        _ => return Reply::Error(format!("Unknown command `{command}`; type `help` for a list")),
+    };
+    let record = runner::run(solution, part, input);
+    match record.answer {
+        Ok(answer) => Reply::Output(format!("{}: {answer} ({:?})", record.label, record.duration)),
+        Err(error) => Reply::Error(error.to_string()),
+    }
+}
+
+/// Reads commands from `reader` until `quit` or end of input, writing replies to `writer`
+pub fn run(solution: &dyn Solution, input: &str, mut reader: impl BufRead, mut writer: impl Write) -> io::Result<()> {
+    writeln!(writer, "Day {}: {} (type `help` for commands)", solution.day(), solution.title())?;
+    let mut explorer = match panic::catch_unwind(AssertUnwindSafe(|| solution.explore(input))) {
+        Ok(Ok(explorer)) => explorer,
+        Ok(Err(error)) => {
+            writeln!(writer, "{}", error.in_day(solution.day()).diagnostic())?;
+            None
+        }
+        Err(payload) => {
+            writeln!(writer, "error: Could not parse the input: {}", panic_message(payload))?;
+            None
+        }
+    };
+    let mut line = String::new();
+    loop {
+        write!(writer, "day{:02}> ", solution.day())?;
+        writer.flush()?;
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            writeln!(writer)?;
+            return Ok(());
+        }
+        match execute(solution, &mut explorer, input, &line) {
+            Reply::Output(output) if output.is_empty() => {}
+            Reply::Output(output) => writeln!(writer, "{output}")?,
+            Reply::Error(error) => writeln!(writer, "error: {error}")?,
+            Reply::Quit => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::find;
+
+    use rstest::rstest;
+
+    fn session(day: usize, commands: &str) -> String {
+        let solution = find(day).unwrap();
+        let mut output = Vec::new();
+        run(solution, solution.example(1).unwrap(), commands.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    pub fn test_generic_commands() {
+        let output = session(9, "\nhelp\npart1\nbogus\nquit\npart2\n");
+        assert!(output.starts_with("Day 9: Rope Bridge"));
+        assert!(output.contains("  set knots <n>"));
+        assert!(output.contains("Tail Position Count(1): 13 ("));
+        assert!(output.contains("error: Unknown command `bogus`"));
+        assert!(!output.contains("Tail Position Count(10)"));
+    }
+
+    #[rstest]
+    #[case(5, "show stacks", "1: Z N\n2: M C D\n3: P")]
+    #[case(5, "step 1\nshow stacks", "1: Z N D\n2: M C\n3: P")]
+    #[case(5, "set crane 9001\nstep 2\nshow stacks", "1: \n2: M C\n3: P Z N D")]
+    #[case(5, "set crane 9002", "error: Unknown crane `9002` (expected 9000 or 9001)")]
+    #[case(7, "dump sizes 100000", "/a   | dir  | 94853\n/a/e | dir  | 584\nday07>")]
+    #[case(8, "score 3 2", "Scenic score at (3, 2): 8")]
+    #[case(8, "score 9 9", "error: No tree at (9, 9)")]
+    #[case(9, "set knots 10\ntail", "Tail visits 1 positions with 10 knots")]
+    #[case(9, "set knots 0\ntail", "error: The rope needs at least 1 knot")]
+    #[case(9, "set knots two", "error: `two` is not a number of knots")]
+    pub fn test_day_commands(#[case] day: usize, #[case] commands: &str, #[case] expected: &str) {
+        let output = session(day, commands);
+        assert!(output.contains(expected), "{output}");
+    }
+}
@@ -0,0 +1,213 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde_json::{json, Value};
+
+use crate::{table, AocError, Part, Solution};
+
+/// The outcome of running one part of one day
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub day: usize,
+    pub part: Part,
+    pub label: &'static str,
+    /// The answer, or why the solver failed (including panics)
+    pub answer: Result<String, AocError>,
+    pub duration: Duration,
+}
+
+impl Record {
+    pub fn status(&self) -> &'static str {
+        match self.answer {
+            Ok(_) => "ok",
+            Err(_) => "failed",
+        }
+    }
+
+    /// A flat JSON object; `duration` is in seconds and exactly one of `answer` / `error` is non-null
+    pub fn to_json(&self) -> Value {
+        let (answer, error) = match &self.answer {
+            Ok(answer) => (Some(answer), None),
+            Err(error) => (None, Some(error.to_string())),
+        };
+        json!({
+            "day": self.day,
+            "part": self.part.number(),
+            "label": self.label,
+            "answer": answer,
+            "duration": self.duration.as_secs_f64(),
+            "error": error,
+        })
+    }
+}
+
+/// Runs a single part, capturing a panic as a failed record instead of unwinding
+pub fn run(solution: &dyn Solution, part: Part, input: &str) -> Record {
+    let _span = tracing::info_span!(target: crate::metrics::TARGET, "solve", day = solution.day(), part = part.number()).entered();
+    let start = Instant::now();
+    let answer = panic::catch_unwind(AssertUnwindSafe(|| solution.solve(part, input))).unwrap_or_else(|payload| Err(AocError::panic(panic_message(payload)).in_day(solution.day())));
+    Record {
+        day: solution.day(),
+        part,
+        label: solution.label(part),
+        answer,
+        duration: start.elapsed(),
+    }
+}
+
+/// Runs each `(solution, part, input)` task on a pool of `jobs` threads,
+/// returning the records in task order however they were scheduled
+pub fn run_parallel(tasks: &[(&dyn Solution, Part, &str)], jobs: usize) -> Vec<Record> {
+    let next = AtomicUsize::new(0);
+    let records = Mutex::new(vec![None; tasks.len()]);
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, tasks.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some((solution, part, input)) = tasks.get(index) else {
+                    break;
+                };
+                let record = run(*solution, *part, input);
+                records.lock().unwrap()[index] = Some(record);
+            });
+        }
+    });
+    records
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|record| record.expect("Every task runs exactly once"))
+        .collect()
+}
+
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Renders records as an aligned table with day, part, answer and status columns
+pub fn table(records: &[Record]) -> String {
+    let rows = records
+        .iter()
+        .map(|record| {
+            let answer = match &record.answer {
+                Ok(answer) => answer.clone(),
+                Err(error) => error.to_string(),
+            };
+            [record.day.to_string(), record.part.to_string(), answer, record.status().to_string()]
+        })
+        .collect::<Vec<_>>();
+    table::render(["Day", "Part", "Answer", "Status"], &rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorKind, SOLUTIONS};
+
+    struct Broken;
+
+    impl Solution for Broken {
+        fn day(&self) -> usize {
+            42
+        }
+        fn title(&self) -> &'static str {
+            "Broken"
+        }
+        fn input(&self) -> &'static str {
+            ""
+        }
+        fn part1(&self, input: &str) -> Result<String, AocError> {
+            match input.find('!') {
+                Some(column) => Err(AocError::parse(0, input, &input[column..], "unexpected `!`")),
+                None => Ok(input.len().to_string()),
+            }
+        }
+        fn part2(&self, _input: &str) -> Result<String, AocError> {
+            panic!("Could not parse value")
+        }
+    }
+
+    #[test]
+    pub fn test_run_captures_panic() {
+        let ok = run(&Broken, Part::One, "abc");
+        assert_eq!(ok.answer, Ok("3".to_string()));
+        assert_eq!(ok.status(), "ok");
+        let failed = run(&Broken, Part::Two, "abc");
+        assert_eq!(failed.answer, Err(AocError::panic("Could not parse value").in_day(42)));
+        assert_eq!(failed.status(), "failed");
+    }
+
+    #[test]
+    pub fn test_run_returns_error() {
+        let failed = run(&Broken, Part::One, "ab!");
+        let error = failed.answer.unwrap_err();
+        assert_eq!((error.kind, error.day, error.line, error.column), (ErrorKind::Parse, Some(42), Some(1), Some(3)));
+    }
+
+    #[test]
+    pub fn test_run_parallel_keeps_task_order() {
+        let tasks = SOLUTIONS
+            .iter()
+            .flat_map(|solution| [Part::One, Part::Two].map(|part| (*solution, part, solution.example(1).unwrap())))
+            .chain([(&Broken as &dyn Solution, Part::Two, "abc"), (&Broken as &dyn Solution, Part::One, "abc")])
+            .collect::<Vec<_>>();
+        let answers = |records: Vec<Record>| {
+            records
+                .into_iter()
+                .map(|record| (record.day, record.part, record.answer))
+                .collect::<Vec<_>>()
+        };
+        let sequential = answers(
+            tasks
+                .iter()
+                .map(|(solution, part, input)| run(*solution, *part, input))
+                .collect(),
+        );
+        assert_eq!(answers(run_parallel(&tasks, 4)), sequential);
+        assert_eq!(answers(run_parallel(&tasks, 0)), sequential);
+        assert!(run_parallel(&[], 4).is_empty());
+    }
+
+    #[test]
+    pub fn test_to_json() {
+        let mut record = run(&Broken, Part::Two, "abc");
+        record.duration = Duration::from_millis(1500);
+        let expected = json!({
+            "day": 42,
+            "part": 2,
+            "label": "Part 2",
+            "answer": null,
+            "duration": 1.5,
+            "error": "Could not parse value",
+        });
+        assert_eq!(record.to_json(), expected);
+    }
+
+    #[test]
+    pub fn test_table() {
+        let records = [run(&Broken, Part::One, "abc"), run(&Broken, Part::Two, "abc")];
+        let expected = textwrap::dedent(
+            "
+            Day | Part | Answer                | Status
+            --- | ---- | --------------------- | ------
+            42  | 1    | 3                     | ok
+            42  | 2    | Could not parse value | failed
+            ",
+        );
+        assert_eq!(table(&records), expected.trim_start());
+    }
+}
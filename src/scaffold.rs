@@ -0,0 +1,247 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use crate::calendar::{LAST_DAY, YEAR};
+
+const MODULE_TEMPLATE: &str = r#"mod {slug};
+
+use crate::{AocError, Example, Solution};
+
+pub struct Day{nn};
+
+impl Solution for Day{nn} {
+    fn day(&self) -> usize {
+        {day}
+    }
+    fn title(&self) -> &'static str {
+        "{title}"
+    }
+    fn input(&self) -> &'static str {
+        include_str!("input.txt")
+    }
+    // Fill in the answers from the puzzle description
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: include_str!("example.txt"),
+            part1: None,
+            part2: None,
+        }]
+    }
+    // Drop once both parts are solved and their answers are in answers.toml
+    fn solved(&self) -> bool {
+        false
+    }
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok({slug}::part1(input)?.to_string())
+    }
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok({slug}::part2(input)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Drop the `ignore` once the examples have answers
+    crate::solution::example_tests!(super::Day{nn}, ignore = "no example answers yet");
+}
+"#;
+
+const SOLVER_TEMPLATE: &str = r#"use crate::AocError;
+
+pub fn part1(input: &str) -> Result<usize, AocError> {
+    Err(AocError::no_answer(format!("Day {day} part 1 is not solved yet ({} lines)", input.lines().count())))
+}
+
+pub fn part2(input: &str) -> Result<usize, AocError> {
+    Err(AocError::no_answer(format!("Day {day} part 2 is not solved yet ({} lines)", input.lines().count())))
+}
+"#;
+
+const README_TEMPLATE: &str = "# {title}\n\n* Link: https://adventofcode.com/{year}/day/{day}\n";
+
+/// `cathode-ray` and `cathode_ray` both become the module name `cathode_ray`
+pub fn slug(name: &str) -> String {
+    name.trim()
+        .to_ascii_lowercase()
+        .replace(['-', ' '], "_")
+}
+
+/// `cathode_ray` becomes `Cathode Ray`
+pub fn title(slug: &str) -> String {
+    slug.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars)
+                    .collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn render(template: &str, day: usize, slug: &str) -> String {
+    template
+        .replace("{nn}", &format!("{day:02}"))
+        .replace("{day}", &day.to_string())
+        .replace("{year}", &YEAR.to_string())
+        .replace("{slug}", slug)
+        .replace("{title}", &title(slug))
+}
+
+/// Adds `pub mod dayNN;` and the `SOLUTIONS` entry to the text of `lib.rs`.
+/// Days are registered in order without gaps, so `day` must be the next one.
+pub fn register(lib: &str, day: usize) -> io::Result<String> {
+    if !(1..=LAST_DAY).contains(&day) {
+        return Err(io::Error::new(ErrorKind::InvalidInput, format!("Day {day} is not a day of the event (expected 1-{LAST_DAY})")));
+    }
+    let module = format!("day{day:02}");
+    let mod_line = format!("pub mod {module};");
+    if lib.lines().any(|line| line == mod_line) {
+        return Err(io::Error::new(ErrorKind::AlreadyExists, format!("{module} is already registered")));
+    }
+    let next = lib
+        .lines()
+        .filter_map(|line| {
+            line.strip_prefix("pub mod day")?
+                .strip_suffix(';')?
+                .parse::<usize>()
+                .ok()
+        })
+        .max()
+        .unwrap_or(0)
+        + 1;
+    if day != next {
+        return Err(io::Error::new(ErrorKind::InvalidInput, format!("Day {day} would leave a gap in the registry; the next day is {next}")));
+    }
+    let invalid = |message: &str| io::Error::new(ErrorKind::InvalidData, format!("Could not register {module}: {message}"));
+
+    let mut lines = lib.lines().map(String::from).collect::<Vec<_>>();
+    // Keep the day modules in order, after the last one that sorts before the new day
+    let mod_index = lines
+        .iter()
+        .rposition(|line| line.starts_with("pub mod day") && line.as_str() < mod_line.as_str())
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    lines.insert(mod_index, mod_line);
+
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub static SOLUTIONS"))
+        .ok_or_else(|| invalid("no SOLUTIONS registry"))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line.trim() == "];")
+            .ok_or_else(|| invalid("unterminated SOLUTIONS registry"))?;
+    lines.insert(end, format!("    &{module}::Day{day:02},"));
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    Ok(text)
+}
+
+/// Writes the `src/dayNN` skeleton under `root` and registers it in `src/lib.rs`
+pub fn create(root: &Path, day: usize, name: &str) -> io::Result<Vec<PathBuf>> {
+    let slug = slug(name);
+    if slug.is_empty()
+        || !slug
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        || slug.starts_with(|c: char| c.is_ascii_digit())
+    {
+        return Err(io::Error::new(ErrorKind::InvalidInput, format!("`{name}` is not a valid module name")));
+    }
+    let dir = root.join("src").join(format!("day{day:02}"));
+    if dir.exists() {
+        return Err(io::Error::new(ErrorKind::AlreadyExists, format!("{} already exists", dir.display())));
+    }
+    let lib_path = root.join("src").join("lib.rs");
+    let lib = register(&fs::read_to_string(&lib_path)?, day)?;
+
+    fs::create_dir_all(&dir)?;
+    let files = [
+        (dir.join("mod.rs"), render(MODULE_TEMPLATE, day, &slug)),
+        (dir.join(format!("{slug}.rs")), render(SOLVER_TEMPLATE, day, &slug)),
+        (dir.join("README.md"), render(README_TEMPLATE, day, &slug)),
+        (dir.join("input.txt"), String::new()),
+        (dir.join("example.txt"), String::new()),
+    ];
+    for (path, contents) in files.iter() {
+        fs::write(path, contents)?;
+    }
+    fs::write(&lib_path, lib)?;
+
+    let mut written = files
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect::<Vec<_>>();
+    written.push(lib_path);
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("cathode-ray", "cathode_ray", "Cathode Ray")]
+    #[case("Monkey Math", "monkey_math", "Monkey Math")]
+    #[case("rope_bridge", "rope_bridge", "Rope Bridge")]
+    pub fn test_slug_and_title(#[case] name: &str, #[case] expected_slug: &str, #[case] expected_title: &str) {
+        let actual = slug(name);
+        assert_eq!(actual, expected_slug);
+        assert_eq!(title(&actual), expected_title);
+    }
+
+    const LIB: &str = "pub mod day01;\npub mod day02;\npub mod solution;\n\npub static SOLUTIONS: &[&dyn Solution] = &[\n    &day01::Day01,\n    &day02::Day02,\n];\n";
+
+    #[test]
+    pub fn test_register() {
+        let expected =
+            "pub mod day01;\npub mod day02;\npub mod day03;\npub mod solution;\n\npub static SOLUTIONS: &[&dyn Solution] = &[\n    &day01::Day01,\n    &day02::Day02,\n    &day03::Day03,\n];\n";
+        assert_eq!(register(LIB, 3).unwrap(), expected);
+        assert_eq!(register(LIB, 2).unwrap_err().kind(), ErrorKind::AlreadyExists);
+    }
+
+    #[rstest]
+    #[case(0, "Day 0 is not a day of the event (expected 1-25)")]
+    #[case(26, "Day 26 is not a day of the event (expected 1-25)")]
+    #[case(100, "Day 100 is not a day of the event (expected 1-25)")]
+    #[case(10, "Day 10 would leave a gap in the registry; the next day is 3")]
+    pub fn test_register_rejects_day(#[case] day: usize, #[case] message: &str) {
+        let error = register(LIB, day).unwrap_err();
+        assert_eq!((error.kind(), error.to_string()), (ErrorKind::InvalidInput, message.to_string()));
+    }
+
+    #[test]
+    pub fn test_create() {
+        let root = env::temp_dir().join(format!("aoc-2022-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src").join("lib.rs"), LIB).unwrap();
+
+        let written = create(&root, 3, "cathode-ray").unwrap();
+        let module = fs::read_to_string(root.join("src/day03/mod.rs")).unwrap();
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        let again = create(&root, 3, "cathode-ray");
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(written.len(), 6);
+        assert!(module.starts_with("mod cathode_ray;\n"));
+        assert!(module.contains("pub struct Day03;"));
+        assert!(module.contains("\"Cathode Ray\""));
+        assert!(lib.contains("&day03::Day03,"));
+        assert_eq!(again.unwrap_err().kind(), ErrorKind::AlreadyExists);
+    }
+}
@@ -0,0 +1,496 @@
+use std::{
+    borrow::Cow,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde_json::{json, Value};
+use tracing::{debug, info, warn};
+
+use crate::{find, input::normalize, runner, ErrorKind, Part, SOLUTIONS};
+
+/// Largest request body accepted, well above any puzzle input
+const MAX_BODY: usize = 16 * 1024 * 1024;
+
+/// Longest request line or header line accepted, line break included
+const MAX_LINE: usize = 8 * 1024;
+
+/// Most header lines accepted in one request
+const MAX_HEADERS: usize = 64;
+
+/// Connections served at once; further clients wait in the listen backlog
+pub const DEFAULT_WORKERS: usize = 8;
+
+/// How long a client may take to send its whole request and read the response
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A parsed HTTP request; only what routing needs
+#[derive(Debug, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+/// A JSON response
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn new(status: u16, body: Value) -> Self {
+        Self { status, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self::new(status, json!({ "error": message.into() }))
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        422 => "Unprocessable Entity",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Reads one line of at most `MAX_LINE` bytes, or `None` when it is longer
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    reader
+        .by_ref()
+        .take(MAX_LINE as u64)
+        .read_line(&mut line)?;
+    match line.len() == MAX_LINE && !line.ends_with('\n') {
+        true => Ok(None),
+        false => Ok(Some(line)),
+    }
+}
+
+/// Reads one request, or `Err` with the response to send instead
+pub fn read_request(reader: &mut impl BufRead) -> io::Result<Result<Request, Response>> {
+    let Some(line) = read_line(reader)? else {
+        return Ok(Err(Response::error(400, format!("Request line is over {MAX_LINE} bytes"))));
+    };
+    let mut words = line.split_whitespace();
+    let (Some(method), Some(path)) = (words.next(), words.next()) else {
+        return Ok(Err(Response::error(400, "Malformed request line")));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+    let mut length = 0;
+    for count in 0.. {
+        let Some(header) = read_line(reader)? else {
+            return Ok(Err(Response::error(431, format!("Header line is over {MAX_LINE} bytes"))));
+        };
+        if header.trim().is_empty() {
+            break;
+        }
+        if count == MAX_HEADERS {
+            return Ok(Err(Response::error(431, format!("Request has over {MAX_HEADERS} headers"))));
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                match value.trim().parse::<usize>() {
+                    Ok(value) => length = value,
+                    Err(_) => return Ok(Err(Response::error(400, "Invalid Content-Length"))),
+                }
+            }
+        }
+    }
+    if length > MAX_BODY {
+        return Ok(Err(Response::error(413, format!("Request body is over {MAX_BODY} bytes"))));
+    }
+    // Grows with the bytes that actually arrive rather than the claimed length
+    let mut body = Vec::new();
+    reader
+        .by_ref()
+        .take(length as u64)
+        .read_to_end(&mut body)?;
+    if body.len() < length {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Request body ended early"));
+    }
+    Ok(Ok(Request { method, path, body }))
+}
+
+/// `GET /days`: every registered solution
+fn days() -> Response {
+    let days = SOLUTIONS
+        .iter()
+        .map(|solution| {
+            json!({
+                "day": solution.day(),
+                "title": solution.title(),
+                "labels": solution.labels(),
+                "examples": solution.examples().len(),
+            })
+        })
+        .collect::<Vec<_>>();
+    Response::new(200, json!({ "days": days }))
+}
+
+/// `POST /days/{day}/parts/{part}`: solves the body as puzzle input
+fn solve(day: &str, part: &str, body: &[u8]) -> Response {
+    let Some(solution) = day.parse::<usize>().ok().and_then(find) else {
+        return Response::error(404, format!("Day not found: `{day}`"));
+    };
+    let part = match part.parse::<Part>() {
+        Ok(part) => part,
+        Err(error) => return Response::error(404, error),
+    };
+    let input = match std::str::from_utf8(body) {
+        Ok(input) if input.trim().is_empty() => return Response::error(400, "Send the puzzle input as the request body"),
+        // The same text the CLI would solve for this input saved as a file
+        Ok(input) => normalize(Cow::Borrowed(input)),
+        Err(error) => return Response::error(400, format!("Puzzle input is not UTF-8: {error}")),
+    };
+    let record = runner::run(solution, part, &input);
+    let mut body = record.to_json();
+    body["status"] = json!(record.status());
+    let status = match &record.answer {
+        Ok(_) => 200,
+        Err(error) => {
+            body["kind"] = json!(error.kind.to_string());
+            body["line"] = json!(error.line);
+            body["column"] = json!(error.column);
+            match error.kind {
+                ErrorKind::Panic => 500,
+                _ => 422,
+            }
+        }
+    };
+    Response::new(status, body)
+}
+
+/// Maps a request to its response
+pub fn route(request: &Request) -> Response {
+    let path = request.path.split('?').next().unwrap_or_default();
+    let segments = path
+        .trim_matches('/')
+        .split('/')
+        .collect::<Vec<_>>();
+    match (request.method.as_str(), &segments[..]) {
+        ("GET", ["days"]) => days(),
+        ("POST", ["days", day, "parts", part]) => solve(day, part, &request.body),
+        (_, ["days"]) | (_, ["days", _, "parts", _]) => Response::error(405, format!("{} is not allowed on {path}", request.method)),
+        _ => Response::error(404, format!("No such endpoint: {path}")),
+    }
+}
+
+/// A stream whose reads all end by one deadline, however the client paces its bytes
+struct Deadline {
+    stream: TcpStream,
+    deadline: Instant,
+}
+
+impl Deadline {
+    fn remaining(&self) -> io::Result<Duration> {
+        match self
+            .deadline
+            .checked_duration_since(Instant::now())
+        {
+            Some(remaining) if !remaining.is_zero() => Ok(remaining),
+            _ => Err(io::Error::new(io::ErrorKind::TimedOut, "Connection deadline passed")),
+        }
+    }
+}
+
+impl Read for Deadline {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.stream
+            .set_read_timeout(Some(self.remaining()?))?;
+        self.stream.read(buf)
+    }
+}
+
+fn handle(stream: TcpStream, timeout: Duration) -> io::Result<()> {
+    let deadline = Instant::now() + timeout;
+    let mut reader = BufReader::new(Deadline { stream, deadline });
+    let response = match read_request(&mut reader)? {
+        Ok(request) => {
+            let response = route(&request);
+            info!("{} {} -> {}", request.method, request.path, response.status);
+            response
+        }
+        Err(response) => response,
+    };
+    let body = response.body.to_string();
+    let connection = reader.into_inner();
+    let mut stream = &connection.stream;
+    stream.set_write_timeout(Some(connection.remaining()?))?;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        response.status,
+        reason(response.status),
+        body.len()
+    )?;
+    stream.flush()
+}
+
+/// A blocking HTTP server answering connections on a fixed pool of threads
+pub struct Server {
+    listener: TcpListener,
+    workers: usize,
+    timeout: Duration,
+}
+
+impl Server {
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(address)?,
+            workers: DEFAULT_WORKERS,
+            timeout: DEFAULT_TIMEOUT,
+        })
+    }
+
+    pub fn workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serves requests until the process exits
+    pub fn run(self) {
+        thread::scope(|scope| {
+            for _ in 0..self.workers {
+                // Each worker takes the next connection once it is done with its current one
+                scope.spawn(|| loop {
+                    match self.listener.accept() {
+                        Ok((stream, _)) => {
+                            if let Err(error) = handle(stream, self.timeout) {
+                                debug!("Connection failed: {error}");
+                            }
+                        }
+                        Err(error) => warn!("Could not accept a connection: {error}"),
+                    }
+                });
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    /// Starts a server on a free port and returns its base URL
+    fn start() -> String {
+        start_with(Server::bind("127.0.0.1:0").unwrap())
+    }
+
+    fn start_with(server: Server) -> String {
+        let base_url = format!("http://{}", server.local_addr().unwrap());
+        thread::spawn(move || server.run());
+        base_url
+    }
+
+    /// Status and JSON body, whether or not the status is an error
+    fn call(request: ureq::Request, body: Option<&str>) -> (u16, Value) {
+        let response = match body {
+            Some(body) => request.send_string(body),
+            None => request.call(),
+        };
+        let response = match response {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(error) => panic!("{error}"),
+        };
+        let status = response.status();
+        (status, serde_json::from_str(&response.into_string().unwrap()).unwrap())
+    }
+
+    #[test]
+    pub fn test_list_days() {
+        let base_url = start();
+        let (status, body) = call(ureq::get(&format!("{base_url}/days")), None);
+        assert_eq!(status, 200);
+        let days = body["days"].as_array().unwrap();
+        assert_eq!(days.len(), SOLUTIONS.len());
+        assert_eq!(
+            days[0],
+            json!({ "day": 1, "title": "Calorie Counting", "labels": ["Maximum calories", "Max 3 calories"], "examples": 1 })
+        );
+    }
+
+    #[test]
+    pub fn test_solve() {
+        let base_url = start();
+        let input = find(5).unwrap().example(1).unwrap();
+        let (status, body) = call(ureq::post(&format!("{base_url}/days/5/parts/2")), Some(input));
+        assert_eq!(status, 200);
+        assert_eq!(body["answer"], "MCD");
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["error"], Value::Null);
+        assert!(body["duration"].as_f64().unwrap() >= 0.0);
+    }
+
+    #[test]
+    pub fn test_solve_normalizes_input() {
+        let base_url = start();
+        let input = find(1).unwrap().example(1).unwrap();
+        let crlf = input.replace('\n', "\r\n");
+        for body in [input, input.trim_end(), &crlf, crlf.trim_end()] {
+            let (status, response) = call(ureq::post(&format!("{base_url}/days/1/parts/1")), Some(body));
+            assert_eq!((status, &response["answer"]), (200, &json!("24000")), "{body:?}");
+        }
+
+        // The line break that normalizing adds is not part of a day 6 marker
+        for body in ["abc", "abc\r\n"] {
+            let (status, response) = call(ureq::post(&format!("{base_url}/days/6/parts/1")), Some(body));
+            assert_eq!((status, &response["kind"]), (422, &json!("no answer")), "{body:?}");
+        }
+    }
+
+    #[test]
+    pub fn test_solve_reports_parse_error() {
+        let base_url = start();
+        let (status, body) = call(ureq::post(&format!("{base_url}/days/2/parts/1")), Some("A Y\nA Q\n"));
+        assert_eq!(status, 422);
+        assert_eq!(body["status"], "failed");
+        assert_eq!(body["answer"], Value::Null);
+        assert_eq!(body["error"], "line 2, column 3: Unknown move `Q`");
+        assert_eq!((&body["kind"], &body["line"], &body["column"]), (&json!("parse error"), &json!(2), &json!(3)));
+    }
+
+    #[rstest]
+    #[case("POST", "/days/26/parts/1", "1", 404, "Day not found: `26`")]
+    #[case("POST", "/days/1/parts/3", "1", 404, "Invalid part: `3` (expected 1 or 2)")]
+    #[case("POST", "/days/1/parts/1", "", 400, "Send the puzzle input as the request body")]
+    #[case("GET", "/days/1/parts/1", "", 405, "GET is not allowed on /days/1/parts/1")]
+    #[case("DELETE", "/days", "", 405, "DELETE is not allowed on /days")]
+    #[case("GET", "/nope", "", 404, "No such endpoint: /nope")]
+    pub fn test_errors(#[case] method: &str, #[case] path: &str, #[case] body: &str, #[case] status: u16, #[case] message: &str) {
+        let base_url = start();
+        let request = ureq::request(method, &format!("{base_url}{path}"));
+        let body = (method != "GET").then_some(body);
+        assert_eq!(call(request, body), (status, json!({ "error": message })));
+    }
+
+    #[test]
+    pub fn test_stalled_client_times_out() {
+        let server = Server::bind("127.0.0.1:0")
+            .unwrap()
+            .workers(1)
+            .timeout(Duration::from_millis(200));
+        let address = server.local_addr().unwrap();
+        let base_url = start_with(server);
+
+        // Holds the only worker without ever finishing its request
+        let mut stalled = TcpStream::connect(address).unwrap();
+        stalled
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        stalled
+            .write_all(b"POST /days/1/parts/1 HTTP/1.1\r\n")
+            .unwrap();
+
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(5))
+            .build();
+        let (status, _) = call(agent.get(&format!("{base_url}/days")), None);
+        assert_eq!(status, 200);
+        let mut rest = Vec::new();
+        assert_eq!(stalled.read_to_end(&mut rest).unwrap(), 0);
+    }
+
+    #[test]
+    pub fn test_trickling_client_hits_deadline() {
+        let server = Server::bind("127.0.0.1:0")
+            .unwrap()
+            .workers(1)
+            .timeout(Duration::from_millis(300));
+        let address = server.local_addr().unwrap();
+        let base_url = start_with(server);
+
+        // Each byte arrives well within the timeout, but the request never ends
+        let trickling = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            for byte in b"POST /days/1/parts/1 HTTP/1.1\r\nHost: localhost\r\n"
+                .iter()
+                .cycle()
+                .take(60)
+            {
+                if stream.write_all(&[*byte]).is_err() {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(50));
+            }
+        });
+        thread::sleep(Duration::from_millis(50));
+
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(2))
+            .build();
+        let (status, _) = call(agent.get(&format!("{base_url}/days")), None);
+        assert_eq!(status, 200);
+        trickling.join().unwrap();
+    }
+
+    #[test]
+    pub fn test_read_request() {
+        let raw = "POST /days/1/parts/1 HTTP/1.1\r\nHost: localhost\r\ncontent-length: 4\r\n\r\n1\n2\nextra";
+        let request = read_request(&mut raw.as_bytes())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            request,
+            Request {
+                method: "POST".to_string(),
+                path: "/days/1/parts/1".to_string(),
+                body: b"1\n2\n".to_vec(),
+            }
+        );
+
+        let raw = format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_BODY + 1);
+        let response = read_request(&mut raw.as_bytes())
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(response.status, 413);
+
+        let long = "a".repeat(MAX_LINE);
+        let raw = format!("GET /{long} HTTP/1.1\r\n\r\n");
+        let response = read_request(&mut raw.as_bytes())
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(response.status, 400);
+
+        let raw = format!("GET /days HTTP/1.1\r\nX-Long: {long}\r\n\r\n");
+        let response = read_request(&mut raw.as_bytes())
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(response.status, 431);
+
+        let raw = format!("GET /days HTTP/1.1\r\n{}\r\n", "X-Many: 1\r\n".repeat(MAX_HEADERS + 1));
+        let response = read_request(&mut raw.as_bytes())
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(response.status, 431);
+
+        let raw = format!("GET /days HTTP/1.1\r\n{}\r\n", "X-Many: 1\r\n".repeat(MAX_HEADERS));
+        assert!(read_request(&mut raw.as_bytes()).unwrap().is_ok());
+
+        let raw = "POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nshort";
+        let error = read_request(&mut raw.as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,249 @@
+use std::{fmt, str::FromStr};
+
+use crate::AocError;
+
+/// A single day's puzzle, solved against any input text.
+pub trait Solution: Send + Sync {
+    /// Day of the event (1-25)
+    fn day(&self) -> usize;
+
+    /// Puzzle title
+    fn title(&self) -> &'static str;
+
+    /// Embedded puzzle input
+    fn input(&self) -> &'static str;
+
+    /// Embedded examples from the puzzle description, with their expected answers
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    /// Input of the `number`th example (1-based)
+    fn example(&self, number: usize) -> Option<&'static str> {
+        let index = number.checked_sub(1)?;
+        self.examples()
+            .get(index)
+            .map(|example| example.input)
+    }
+
+    /// False for a scaffolded day whose parts are still stubs; checks that need
+    /// real answers (stored answers, input generators) skip it
+    fn solved(&self) -> bool {
+        true
+    }
+
+    /// Human readable labels for each part's answer
+    fn labels(&self) -> [&'static str; 2] {
+        ["Part 1", "Part 2"]
+    }
+
+    /// Whether the day overrides `parse`; benchmarks skip the phase otherwise
+    fn has_parser(&self) -> bool {
+        false
+    }
+
+    /// Builds the day's parsed structures and throws them away, so parsing can
+    /// be timed on its own.  Days without a separate parse step keep the no-op
+    /// and leave `has_parser` false.
+    fn parse(&self, _input: &str) -> Result<(), AocError> {
+        Ok(())
+    }
+
+    /// Parses `input` into the day's intermediate structures for `repl`.
+    /// Days without anything worth exploring keep the default.
+    fn explore(&self, _input: &str) -> Result<Option<Box<dyn Explore>>, AocError> {
+        Ok(None)
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AocError>;
+
+    fn part2(&self, input: &str) -> Result<String, AocError>;
+
+    fn label(&self, part: Part) -> &'static str {
+        self.labels()[part.index()]
+    }
+
+    /// Solves one part, tagging any error with the day
+    fn solve(&self, part: Part, input: &str) -> Result<String, AocError> {
+        let answer = match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        };
+        answer.map_err(|error| error.in_day(self.day()))
+    }
+}
+
+/// An embedded example input (`example.txt`, `example2.txt`, ...) and the
+/// answers the puzzle description gives for it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+}
+
+impl Example {
+    pub fn answer(&self, part: Part) -> Option<&'static str> {
+        match part {
+            Part::One => self.part1,
+            Part::Two => self.part2,
+        }
+    }
+}
+
+/// Checks every declared example answer; days call it through `example_tests!`
+#[cfg(test)]
+pub(crate) fn check_examples(solution: &dyn Solution) {
+    let answers = solution
+        .examples()
+        .iter()
+        .flat_map(|example| [example.part1, example.part2])
+        .flatten()
+        .count();
+    assert!(answers > 0, "Day {} declares no example answers", solution.day());
+    for (index, example) in solution.examples().iter().enumerate() {
+        for part in [Part::One, Part::Two] {
+            if let Some(expected) = example.answer(part) {
+                let actual = solution.solve(part, example.input);
+                assert_eq!(actual.as_deref(), Ok(expected), "Day {} example {} part {part}", solution.day(), index + 1);
+            }
+        }
+    }
+}
+
+/// Generates a `test_examples` test from a day's declared examples
+#[cfg(test)]
+macro_rules! example_tests {
+    // Scaffolded days have no example answers to check yet
+    ($solution:expr, ignore = $reason:literal) => {
+        #[test]
+        #[ignore = $reason]
+        pub fn test_examples() {
+            crate::solution::check_examples(&$solution);
+        }
+    };
+    ($solution:expr) => {
+        #[test]
+        pub fn test_examples() {
+            crate::solution::check_examples(&$solution);
+        }
+    };
+}
+#[cfg(test)]
+pub(crate) use example_tests;
+
+/// Day-specific `repl` commands over a parsed input
+pub trait Explore {
+    /// `(usage, description)` for each command, listed by `help`
+    fn commands(&self) -> &'static [(&'static str, &'static str)];
+
+    /// Runs `command`, or returns `None` when it is not one of this day's
+    fn run(&mut self, command: &str, args: &[&str]) -> Option<Result<String, String>>;
+}
+
+/// One half of a day's puzzle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(&self) -> usize {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+        }
+    }
+    fn index(&self) -> usize {
+        self.number() - 1
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Self::One),
+            "2" => Ok(Self::Two),
+            _ => Err(format!("Invalid part: `{s}` (expected 1 or 2)")),
+        }
+    }
+}
+
+/// Which parts of a day to run
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Parts {
+    One,
+    Two,
+    #[default]
+    Both,
+}
+
+impl Parts {
+    pub fn iter(&self) -> impl Iterator<Item = Part> {
+        let parts: &'static [Part] = match self {
+            Self::One => &[Part::One],
+            Self::Two => &[Part::Two],
+            Self::Both => &[Part::One, Part::Two],
+        };
+        parts.iter().copied()
+    }
+}
+
+impl fmt::Display for Parts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::One => write!(f, "1"),
+            Self::Two => write!(f, "2"),
+            Self::Both => write!(f, "both"),
+        }
+    }
+}
+
+impl FromStr for Parts {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "both" => Ok(Self::Both),
+            part => match part.parse::<Part>() {
+                Ok(Part::One) => Ok(Self::One),
+                Ok(Part::Two) => Ok(Self::Two),
+                Err(_) => Err(format!("Invalid part: `{s}` (expected 1, 2 or both)")),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("1", Ok(Parts::One))]
+    #[case("2", Ok(Parts::Two))]
+    #[case("both", Ok(Parts::Both))]
+    #[case("Both", Ok(Parts::Both))]
+    #[case("3", Err("Invalid part: `3` (expected 1, 2 or both)".to_string()))]
+    pub fn test_parse_parts(#[case] input: &str, #[case] expected: Result<Parts, String>) {
+        assert_eq!(input.parse::<Parts>(), expected);
+    }
+
+    #[rstest]
+    #[case(Parts::One, &[Part::One])]
+    #[case(Parts::Two, &[Part::Two])]
+    #[case(Parts::Both, &[Part::One, Part::Two])]
+    pub fn test_parts_iter(#[case] parts: Parts, #[case] expected: &[Part]) {
+        assert_eq!(parts.iter().collect::<Vec<_>>(), expected);
+    }
+}
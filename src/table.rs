@@ -0,0 +1,32 @@
+use std::fmt::Write;
+
+/// Renders rows as a left aligned, `|` separated table with a header rule
+pub fn render<const N: usize>(headers: [&str; N], rows: &[[String; N]]) -> String {
+    let mut widths = headers.map(|header| header.chars().count());
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let mut output = String::new();
+    let mut write_row = |cells: [&str; N]| {
+        let line = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        writeln!(output, "{}", line.trim_end()).unwrap();
+    };
+    write_row(headers);
+    write_row(
+        widths
+            .map(|width| "-".repeat(width))
+            .each_ref()
+            .map(|s| s.as_str()),
+    );
+    for row in rows.iter() {
+        write_row(row.each_ref().map(|s| s.as_str()));
+    }
+    output
+}
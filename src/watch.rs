@@ -0,0 +1,137 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{runner::Record, table};
+
+/// Polls a file for changes to its modification time or length
+#[derive(Clone, Debug)]
+pub struct Watcher {
+    path: PathBuf,
+    stamp: Option<(SystemTime, u64)>,
+}
+
+fn stamp(path: &Path) -> io::Result<Option<(SystemTime, u64)>> {
+    match fs::metadata(path) {
+        Ok(metadata) => Ok(Some((metadata.modified()?, metadata.len()))),
+        // Editors often replace a file by removing and recreating it
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+impl Watcher {
+    pub fn new(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            stamp: stamp(path)?,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether the file changed since the last call (or since the watcher was created)
+    pub fn changed(&mut self) -> io::Result<bool> {
+        let stamp = stamp(&self.path)?;
+        if stamp.is_none() || stamp == self.stamp {
+            return Ok(false);
+        }
+        self.stamp = stamp;
+        Ok(true)
+    }
+
+    /// Blocks until the file changes, checking every `interval`
+    pub fn wait(&mut self, interval: Duration) -> io::Result<()> {
+        while !self.changed()? {
+            thread::sleep(interval);
+        }
+        Ok(())
+    }
+}
+
+/// Renders a run next to the previous one, matching records by part
+pub fn compare(previous: &[Record], current: &[Record]) -> String {
+    let answer = |record: &Record| match &record.answer {
+        Ok(answer) => answer.clone(),
+        Err(error) => format!("failed: {error}"),
+    };
+    let rows = current
+        .iter()
+        .map(|record| {
+            let before = previous
+                .iter()
+                .find(|before| before.part == record.part);
+            let change = match before {
+                None => "new",
+                Some(before) if before.answer == record.answer => "same",
+                Some(_) => "changed",
+            };
+            [
+                record.label.to_string(),
+                answer(record),
+                format!("{:?}", record.duration),
+                before.map_or_else(|| "-".to_string(), answer),
+                before.map_or_else(|| "-".to_string(), |before| format!("{:?}", before.duration)),
+                change.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    table::render(["Part", "Answer", "Time", "Previous", "Previous time", "Change"], &rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::{AocError, Part};
+
+    fn record(part: Part, answer: Result<&str, &str>, millis: u64) -> Record {
+        Record {
+            day: 1,
+            part,
+            label: ["Part 1", "Part 2"][part.number() - 1],
+            answer: answer.map(String::from).map_err(AocError::panic),
+            duration: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    pub fn test_changed() {
+        let path = env::temp_dir().join(format!("aoc-2022-watch-{}.txt", std::process::id()));
+        fs::write(&path, "1\n").unwrap();
+        let mut watcher = Watcher::new(&path).unwrap();
+        let unchanged = watcher.changed().unwrap();
+        fs::write(&path, "1\n2\n").unwrap();
+        let changed = watcher.changed().unwrap();
+        let settled = watcher.changed().unwrap();
+        fs::remove_file(&path).unwrap();
+        let removed = watcher.changed().unwrap();
+
+        assert_eq!((unchanged, changed, settled, removed), (false, true, false, false));
+    }
+
+    #[test]
+    pub fn test_compare() {
+        let previous = [record(Part::One, Ok("24000"), 2)];
+        let current = [record(Part::One, Ok("24000"), 1), record(Part::Two, Err("boom"), 3)];
+        let expected = textwrap::dedent(
+            "
+            Part   | Answer       | Time | Previous | Previous time | Change
+            ------ | ------------ | ---- | -------- | ------------- | ------
+            Part 1 | 24000        | 1ms  | 24000    | 2ms           | same
+            Part 2 | failed: boom | 3ms  | -        | -             | new
+            ",
+        );
+        assert_eq!(compare(&previous, &current), expected.trim_start());
+
+        let fixed = [record(Part::Two, Ok("45000"), 1)];
+        assert!(compare(&current, &fixed).contains("| changed"));
+    }
+}
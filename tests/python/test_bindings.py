@@ -0,0 +1,157 @@
+"""Tests for the `python` feature's extension module, run with plain CPython:
+
+    python3 -m unittest discover tests/python
+
+The module is built with
+`cargo rustc --lib --features python --crate-type cdylib` first, unless
+`AOC_2022_LIB` points at an already built library.
+"""
+
+import importlib.util
+import os
+import pathlib
+import shutil
+import subprocess
+import sys
+import tempfile
+import unittest
+
+ROOT = pathlib.Path(__file__).resolve().parents[2]
+
+
+def load():
+    library = os.environ.get("AOC_2022_LIB")
+    if library is None:
+        command = ["cargo", "rustc", "--lib", "--features", "python", "--crate-type", "cdylib"]
+        subprocess.run(command, cwd=ROOT, check=True)
+        name = {"darwin": "libaoc_2022.dylib", "win32": "aoc_2022.dll"}.get(sys.platform, "libaoc_2022.so")
+        library = ROOT / "target" / "debug" / name
+    # Python only imports extension modules named after the module
+    directory = tempfile.mkdtemp()
+    suffix = ".pyd" if sys.platform == "win32" else ".so"
+    module = pathlib.Path(directory) / f"aoc_2022{suffix}"
+    shutil.copy(library, module)
+    spec = importlib.util.spec_from_file_location("aoc_2022", module)
+    aoc_2022 = importlib.util.module_from_spec(spec)
+    sys.modules["aoc_2022"] = aoc_2022
+    spec.loader.exec_module(aoc_2022)
+    return aoc_2022
+
+
+aoc_2022 = load()
+
+
+def example(day, number=""):
+    return (ROOT / "src" / f"day{day:02}" / f"example{number}.txt").read_text()
+
+
+class TestSolve(unittest.TestCase):
+    def test_days(self):
+        self.assertEqual(aoc_2022.days()[0], (1, "Calorie Counting"))
+
+    def test_solve(self):
+        self.assertEqual(aoc_2022.solve(5, 2, example(5)), "MCD")
+        self.assertEqual(aoc_2022.solve(1, 1), aoc_2022.solve(1, 1, None))
+
+    def test_solve_rejects_unknown_day_and_part(self):
+        with self.assertRaisesRegex(ValueError, "Day not found"):
+            aoc_2022.solve(26, 1)
+        with self.assertRaisesRegex(ValueError, "Invalid part"):
+            aoc_2022.solve(1, 3)
+
+
+class TestDays(unittest.TestCase):
+    def test_calories(self):
+        from aoc_2022 import calories
+
+        self.assertEqual(calories.parse("1\n2\n\n3\n"), [[1, 2], [3]])
+        self.assertEqual(calories.calculate(example(1), 1), 24000)
+        self.assertEqual(calories.calculate(example(1), 3), 45000)
+
+    def test_rock_paper(self):
+        from aoc_2022 import rock_paper
+
+        self.assertEqual(rock_paper.play_round_1(example(2)), 15)
+        self.assertEqual(rock_paper.play_round_2(example(2)), 12)
+
+    def test_rucksack(self):
+        from aoc_2022 import rucksack
+
+        self.assertEqual(rucksack.prioritize(example(3)), 157)
+        self.assertEqual(rucksack.find_badge_priority(example(3)), 70)
+
+    def test_camp_cleaning(self):
+        from aoc_2022 import camp_cleaning
+
+        self.assertEqual(camp_cleaning.parse_pairs("2-4,6-8\n")[0], ((2, 4), (6, 8)))
+        self.assertEqual(camp_cleaning.count_fully_contained(example(4)), 2)
+        self.assertEqual(camp_cleaning.count_partial_overlap(example(4)), 4)
+
+    def test_supply_stacks(self):
+        from aoc_2022 import supply_stacks
+
+        self.assertEqual(supply_stacks.parse_stacks(example(5)), [["Z", "N"], ["M", "C", "D"], ["P"]])
+        move = supply_stacks.parse_moves(example(5))[0]
+        self.assertIsInstance(move, supply_stacks.Move)
+        self.assertEqual((move.count, move.origination, move.destination), (1, 2, 1))
+        self.assertEqual(repr(move), "Move(count=1, origination=2, destination=1)")
+        self.assertEqual("".join(supply_stacks.arrange_crates_9000(example(5))), "CMZ")
+        self.assertEqual("".join(supply_stacks.arrange_crates_9001(example(5))), "MCD")
+
+    def test_tuning_trouble(self):
+        from aoc_2022 import tuning_trouble
+
+        self.assertEqual(tuning_trouble.detect_start(example(6), 4), 7)
+        self.assertEqual(tuning_trouble.detect_start(example(6), 14), 19)
+        with self.assertRaises(aoc_2022.AocError):
+            tuning_trouble.detect_start(example(6), 0)
+
+    def test_no_space(self):
+        from aoc_2022 import no_space
+
+        sizes = no_space.build_sizes(example(7))
+        self.assertTrue(sizes["/a/e"].is_dir)
+        self.assertEqual(sizes["/a/e"].size, 584)
+        self.assertFalse(sizes["/b.txt"].is_dir)
+        self.assertEqual(no_space.find_good_deletion_candidates(example(7)), 95437)
+        self.assertEqual(no_space.folder_to_delete(example(7)), 24933642)
+
+    def test_tree_top(self):
+        from aoc_2022 import tree_top
+
+        self.assertEqual(tree_top.parse_tree(example(8))[0], [3, 0, 3, 7, 3])
+        self.assertEqual(tree_top.find_trees_visible(example(8)), 21)
+        self.assertEqual(tree_top.find_highest_scenic_score(example(8)), 8)
+
+    def test_rope_bridge(self):
+        import aoc_2022.rope_bridge as rope_bridge
+
+        update = rope_bridge.parse_moves("R 2\n")[1]
+        self.assertEqual((update.direction, update.distance), ("Right", 1))
+        self.assertEqual(rope_bridge.count_tail_position(example(9), 2), 13)
+        self.assertEqual(rope_bridge.count_tail_position(example(9, 2), 10), 36)
+        with self.assertRaises(aoc_2022.AocError):
+            rope_bridge.count_tail_position(example(9), 0)
+
+
+class TestErrors(unittest.TestCase):
+    def test_parse_error(self):
+        from aoc_2022 import rock_paper
+
+        with self.assertRaises(aoc_2022.AocError) as raised:
+            rock_paper.play_round_1("A Y\nA Q\n")
+        error = raised.exception
+        self.assertIsInstance(error, ValueError)
+        self.assertEqual(str(error), "line 2, column 3: Unknown move `Q`")
+        self.assertEqual((error.kind, error.line, error.column), ("parse error", 2, 3))
+
+    def test_no_answer(self):
+        from aoc_2022 import tuning_trouble
+
+        with self.assertRaises(aoc_2022.AocError) as raised:
+            tuning_trouble.detect_start("aabb", 4)
+        self.assertEqual((raised.exception.kind, raised.exception.line), ("no answer", None))
+
+
+if __name__ == "__main__":
+    unittest.main()